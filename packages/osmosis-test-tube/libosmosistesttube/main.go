@@ -5,6 +5,7 @@ import "C"
 import (
 	// std
 	"encoding/base64"
+	"encoding/binary"
 	"encoding/json"
 	"fmt"
 	"os"
@@ -19,14 +20,13 @@ import (
 	// tendermint
 	abci "github.com/tendermint/tendermint/abci/types"
 	tmproto "github.com/tendermint/tendermint/proto/tendermint/types"
+	dbm "github.com/tendermint/tm-db"
 
 	// cosmos sdk
 
-	codectypes "github.com/cosmos/cosmos-sdk/codec/types"
 	"github.com/cosmos/cosmos-sdk/crypto/keys/secp256k1"
-	"github.com/cosmos/cosmos-sdk/simapp"
 	sdk "github.com/cosmos/cosmos-sdk/types"
-	banktypes "github.com/cosmos/cosmos-sdk/x/bank/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
 
 	// wasmd
 	wasmtypes "github.com/CosmWasm/wasmd/x/wasm/types"
@@ -42,53 +42,232 @@ var (
 	envCounter  uint64 = 0
 	envRegister        = sync.Map{}
 	mu          sync.Mutex
+
+	snapshotCounter  uint64 = 0
+	snapshotRegister        = sync.Map{}
 )
 
+// envSnapshot is a snapshot along with the env it was taken from,
+// snapshots can only be restored to that env and are removed when it is cleaned up.
+type envSnapshot struct {
+	envId    uint64
+	snapshot *testenv.Snapshot
+}
+
 //export InitTestEnv
-func InitTestEnv() uint64 {
+func InitTestEnv(configJson string) *C.char { // => envId
 	// Temp fix for concurrency issue
 	mu.Lock()
 	defer mu.Unlock()
 
-	envCounter += 1
-	id := envCounter
+	cfg, err := testenv.ParseConfig([]byte(configJson))
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	if err := cfg.SetBech32Prefixes(hasEnvs()); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
 
 	nodeHome, err := os.MkdirTemp("", ".osmosis-test-tube-temp-")
 	if err != nil {
 		panic(err)
 	}
 
+	db := dbm.NewMemDB()
+
 	env := new(testenv.TestEnv)
-	env.App = testenv.SetupOsmosisApp(nodeHome)
+	env.App, err = testenv.SetupOsmosisApp(nodeHome, db, cfg)
+	if err != nil {
+		os.RemoveAll(nodeHome)
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+	env.DB = db
 	env.NodeHome = nodeHome
 	env.ParamTypesRegistry = *testenv.NewParamTypeRegistry()
+	env.Config = cfg
+	env.BlockInterval = testenv.DefaultBlockInterval
 
 	env.SetupParamTypes()
 
 	// Allow testing unoptimized contract
 	wasmtypes.MaxWasmSize = 1024 * 1024 * 1024 * 1024 * 1024
 
-	env.Ctx = env.App.BaseApp.NewContext(false, tmproto.Header{Height: 0, ChainID: "osmosis-1", Time: time.Now().UTC()})
+	env.Ctx = env.App.BaseApp.NewContext(false, tmproto.Header{Height: 0, ChainID: cfg.ChainID, Time: cfg.GenesisTimeUTC()})
+
+	if err := env.ApplyConfig(cfg); err != nil {
+		os.RemoveAll(nodeHome)
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	env.BeginNextBlock()
+
+	reqEndBlock := abci.RequestEndBlock{Height: env.Ctx.BlockHeight()}
+	env.App.EndBlock(reqEndBlock)
+	env.App.Commit()
+
+	envCounter += 1
+	id := envCounter
+
+	envRegister.Store(id, *env)
+
+	return encodeUint64ResultBytes(id)
+}
+
+//export InitTestEnvFromGenesis
+func InitTestEnvFromGenesis(configJson, genesisJson string) *C.char { // => envId
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	cfg, err := testenv.ParseConfig([]byte(configJson))
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	if err := cfg.SetBech32Prefixes(hasEnvs()); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	nodeHome, err := os.MkdirTemp("", ".osmosis-test-tube-temp-")
+	if err != nil {
+		panic(err)
+	}
+
+	// Allow testing unoptimized contract
+	wasmtypes.MaxWasmSize = 1024 * 1024 * 1024 * 1024 * 1024
+
+	env, err := testenv.NewTestEnvFromGenesis(nodeHome, cfg, []byte(genesisJson))
+	if err != nil {
+		os.RemoveAll(nodeHome)
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
 
-	env.BeginNewBlock(false, 5)
+	env.BeginNextBlock()
 
 	reqEndBlock := abci.RequestEndBlock{Height: env.Ctx.BlockHeight()}
 	env.App.EndBlock(reqEndBlock)
 	env.App.Commit()
 
+	envCounter += 1
+	id := envCounter
+
 	envRegister.Store(id, *env)
 
-	return id
+	return encodeUint64ResultBytes(id)
+}
+
+//export ExportGenesis
+func ExportGenesis(envId uint64) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	bz, err := env.ExportGenesis()
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	return encodeBytesResultBytes(bz)
 }
 
 //export CleanUp
 func CleanUp(envId uint64) {
 	env := loadEnv(envId)
-	err := os.RemoveAll(env.NodeHome)
-	if err != nil {
+	if err := env.Close(); err != nil {
 		panic(err)
 	}
 	envRegister.Delete(envId)
+
+	snapshotRegister.Range(func(key, value any) bool {
+		s := value.(envSnapshot)
+		if s.envId == envId {
+			if err := s.snapshot.CleanUp(); err != nil {
+				panic(err)
+			}
+			snapshotRegister.Delete(key)
+		}
+		return true
+	})
+}
+
+//export Fork
+func Fork(envId uint64) *C.char { // => envId
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	forked, err := env.Fork()
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	envCounter += 1
+	id := envCounter
+
+	envRegister.Store(id, *forked)
+
+	return encodeUint64ResultBytes(id)
+}
+
+//export Snapshot
+func Snapshot(envId uint64) *C.char { // => snapshotId
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	snapshot, err := env.TakeSnapshot()
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	snapshotCounter += 1
+	id := snapshotCounter
+
+	snapshotRegister.Store(id, envSnapshot{envId: envId, snapshot: snapshot})
+
+	return encodeUint64ResultBytes(id)
+}
+
+//export Restore
+func Restore(envId uint64, snapshotId uint64) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	if env.BlockInProgress {
+		err := errors.New("cannot restore snapshot while a block is in progress")
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	item, ok := snapshotRegister.Load(snapshotId)
+	if !ok || item.(envSnapshot).envId != envId {
+		err := fmt.Errorf("snapshot %d not found for env %d", snapshotId, envId)
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	restored, err := item.(envSnapshot).snapshot.NewTestEnv()
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	// the replaced env is not used anymore, release it before installing the restored one
+	if err := env.Close(); err != nil {
+		panic(err)
+	}
+
+	// block time settings are kept across restores
+	restored.BlockInterval = env.BlockInterval
+	restored.NextBlockTime = env.NextBlockTime
+
+	envRegister.Store(envId, *restored)
+
+	// return empty bytes if no error
+	return encodeBytesResultBytes([]byte{})
 }
 
 //export InitAccount
@@ -103,39 +282,107 @@ func InitAccount(envId uint64, coinsJson string) *C.char {
 	priv := secp256k1.GenPrivKey()
 	accAddr := sdk.AccAddress(priv.PubKey().Address())
 
-	for _, coin := range coins {
-		// create denom if not exist
-		_, hasDenomMetaData := env.App.BankKeeper.GetDenomMetaData(env.Ctx, coin.Denom)
-		if !hasDenomMetaData {
-			denomMetaData := banktypes.Metadata{
-				DenomUnits: []*banktypes.DenomUnit{{
-					Denom:    coin.Denom,
-					Exponent: 0,
-				}},
-				Base: coin.Denom,
-			}
+	err := env.FundAccount(accAddr, coins)
+	if err != nil {
+		panic(errors.Wrapf(err, "Failed to fund account"))
+	}
 
-			env.App.BankKeeper.SetDenomMetaData(env.Ctx, denomMetaData)
-		}
+	base64Priv := base64.StdEncoding.EncodeToString(priv.Bytes())
+
+	envRegister.Store(envId, env)
+
+	return C.CString(base64Priv)
+}
+
+//export InitAccounts
+func InitAccounts(envId uint64, coinsJson string, count uint64) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
 
+	var coins sdk.Coins
+	if err := json.Unmarshal([]byte(coinsJson), &coins); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
 	}
 
-	err := simapp.FundAccount(env.App.BankKeeper, env.Ctx, accAddr, coins)
+	addrs := make([]sdk.AccAddress, 0, count)
+	// private keys are 32 bytes each, concatenated in account order
+	privs := make([]byte, 0, count*secp256k1.PrivKeySize)
+	for i := uint64(0); i < count; i++ {
+		priv := secp256k1.GenPrivKey()
+		addrs = append(addrs, sdk.AccAddress(priv.PubKey().Address()))
+		privs = append(privs, priv.Bytes()...)
+	}
+
+	if err := env.FundAccounts(addrs, coins); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, errors.Wrapf(err, "Failed to fund accounts"))
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes(privs)
+}
+
+//export FundAccount
+func FundAccount(envId uint64, bech32Address, coinsJson string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	var coins sdk.Coins
+	if err := json.Unmarshal([]byte(coinsJson), &coins); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	addr, err := sdk.AccAddressFromBech32(bech32Address)
 	if err != nil {
-		panic(errors.Wrapf(err, "Failed to fund account"))
+		return encodeErrToResultBytes(result.ExecuteError, err)
 	}
 
-	base64Priv := base64.StdEncoding.EncodeToString(priv.Bytes())
+	if err := env.MintTo(addr, coins); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, errors.Wrapf(err, "Failed to fund account"))
+	}
 
 	envRegister.Store(envId, env)
 
-	return C.CString(base64Priv)
+	// return empty bytes if no error
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export SetBalance
+func SetBalance(envId uint64, bech32Address, coinsJson string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	var coins sdk.Coins
+	if err := json.Unmarshal([]byte(coinsJson), &coins); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	addr, err := sdk.AccAddressFromBech32(bech32Address)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	if err := env.SetBalance(addr, coins); err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, errors.Wrapf(err, "Failed to set balance"))
+	}
+
+	envRegister.Store(envId, env)
+
+	// return empty bytes if no error
+	return encodeBytesResultBytes([]byte{})
 }
 
 //export IncreaseTime
 func IncreaseTime(envId uint64, seconds uint64) {
 	env := loadEnv(envId)
-	env.BeginNewBlock(false, seconds)
+	env.NextBlockTime = nil
+	env.BeginNewBlock(false, time.Duration(seconds)*time.Second)
 	envRegister.Store(envId, env)
 	EndBlock(envId)
 }
@@ -143,7 +390,8 @@ func IncreaseTime(envId uint64, seconds uint64) {
 //export BeginBlock
 func BeginBlock(envId uint64) {
 	env := loadEnv(envId)
-	env.BeginNewBlock(false, 5)
+	env.BeginNextBlock()
+	env.BlockInProgress = true
 	envRegister.Store(envId, env)
 }
 
@@ -153,7 +401,79 @@ func EndBlock(envId uint64) {
 	reqEndBlock := abci.RequestEndBlock{Height: env.Ctx.BlockHeight()}
 	env.App.EndBlock(reqEndBlock)
 	env.App.Commit()
+	env.BlockInProgress = false
+	env.UncommittedChanges = false
+	envRegister.Store(envId, env)
+}
+
+//export SkipBlocks
+func SkipBlocks(envId uint64, n uint64) *C.char {
+	env := loadEnv(envId)
+	if env.BlockInProgress {
+		err := errors.New("cannot skip blocks while a block is in progress")
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	for i := uint64(0); i < n; i++ {
+		BeginBlock(envId)
+		EndBlock(envId)
+	}
+
+	// return empty bytes if no error
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export AdvanceToNextEpoch
+func AdvanceToNextEpoch(envId uint64, identifier string) *C.char {
+	env := loadEnv(envId)
+	if env.BlockInProgress {
+		err := errors.New("cannot advance to next epoch while a block is in progress")
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	nextEpochStartTime, err := env.NextEpochStartTime(identifier)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	env.NextBlockTime = &nextEpochStartTime
+	envRegister.Store(envId, env)
+
+	BeginBlock(envId)
+	EndBlock(envId)
+
+	// return empty bytes if no error
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export SetBlockInterval
+func SetBlockInterval(envId uint64, nanos int64) {
+	env := loadEnv(envId)
+	env.BlockInterval = time.Duration(nanos)
+	envRegister.Store(envId, env)
+}
+
+//export SetNextBlockTime
+func SetNextBlockTime(envId uint64, unixNanos int64) *C.char {
+	env := loadEnv(envId)
+
+	nextBlockTime := time.Unix(0, unixNanos).UTC()
+	if !nextBlockTime.After(env.Ctx.BlockTime()) {
+		err := fmt.Errorf("next block time %s must be after current block time %s", nextBlockTime, env.Ctx.BlockTime())
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	env.NextBlockTime = &nextBlockTime
 	envRegister.Store(envId, env)
+
+	// return empty bytes if no error
+	return encodeBytesResultBytes([]byte{})
+}
+
+//export IsBlockInProgress
+func IsBlockInProgress(envId uint64) bool {
+	env := loadEnv(envId)
+	return env.BlockInProgress
 }
 
 //export Execute
@@ -186,6 +506,97 @@ func Execute(envId uint64, base64ReqDeliverTx string) *C.char {
 	return encodeBytesResultBytes(bz)
 }
 
+//export ExecuteAs
+func ExecuteAs(envId uint64, sender, base64TxBodyBytes string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	txBodyBytes, err := base64.StdEncoding.DecodeString(base64TxBodyBytes)
+	if err != nil {
+		panic(err)
+	}
+
+	senderAddr, err := sdk.AccAddressFromBech32(sender)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	resDeliverTx, err := env.ExecuteAs(senderAddr, txBodyBytes)
+	if err != nil {
+		return encodeTxErrToResultBytes(err, sdk.GasInfo{GasUsed: uint64(resDeliverTx.GasUsed)})
+	}
+
+	bz, err := proto.Marshal(&resDeliverTx)
+	if err != nil {
+		panic(err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes(bz)
+}
+
+//export ExecuteAsContract
+func ExecuteAsContract(envId uint64, contract, base64TxBodyBytes string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	txBodyBytes, err := base64.StdEncoding.DecodeString(base64TxBodyBytes)
+	if err != nil {
+		panic(err)
+	}
+
+	contractAddr, err := sdk.AccAddressFromBech32(contract)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	resDeliverTx, err := env.ExecuteAsContract(contractAddr, txBodyBytes)
+	if err != nil {
+		return encodeTxErrToResultBytes(err, sdk.GasInfo{GasUsed: uint64(resDeliverTx.GasUsed)})
+	}
+
+	bz, err := proto.Marshal(&resDeliverTx)
+	if err != nil {
+		panic(err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes(bz)
+}
+
+//export WasmSudo
+func WasmSudo(envId uint64, contract, msgJson string) *C.char {
+	env := loadEnv(envId)
+	// Temp fix for concurrency issue
+	mu.Lock()
+	defer mu.Unlock()
+
+	contractAddr, err := sdk.AccAddressFromBech32(contract)
+	if err != nil {
+		return encodeErrToResultBytes(result.ExecuteError, err)
+	}
+
+	resDeliverTx, err := env.Sudo(contractAddr, []byte(msgJson))
+	if err != nil {
+		return encodeTxErrToResultBytes(err, sdk.GasInfo{GasUsed: uint64(resDeliverTx.GasUsed)})
+	}
+
+	bz, err := proto.Marshal(&resDeliverTx)
+	if err != nil {
+		panic(err)
+	}
+
+	envRegister.Store(envId, env)
+
+	return encodeBytesResultBytes(bz)
+}
+
 //export Query
 func Query(envId uint64, path, base64QueryMsgBytes string) *C.char {
 	env := loadEnv(envId)
@@ -271,7 +682,7 @@ func Simulate(envId uint64, base64TxBytes string) *C.char { // => base64GasInfo
 	gasInfo, _, err := env.App.Simulate(txBytes)
 
 	if err != nil {
-		return encodeErrToResultBytes(result.ExecuteError, err)
+		return encodeTxErrToResultBytes(err, gasInfo)
 	}
 
 	bz, err := proto.Marshal(&gasInfo)
@@ -295,29 +706,10 @@ func SetParamSet(envId uint64, subspaceName, base64ParamSetBytes string) *C.char
 		panic(err)
 	}
 
-	subspace, ok := env.App.ParamsKeeper.GetSubspace(subspaceName)
-	if !ok {
-		err := errors.New("No subspace found for `" + subspaceName + "`")
-		return encodeErrToResultBytes(result.ExecuteError, err)
-	}
-
-	pReg := env.ParamTypesRegistry
-
-	any := codectypes.Any{}
-	err = proto.Unmarshal(paramSetBytes, &any)
-
-	if err != nil {
-		return encodeErrToResultBytes(result.ExecuteError, err)
-	}
-
-	pset, err := pReg.UnpackAny(&any)
-
-	if err != nil {
+	if err := env.SetParamSet(subspaceName, paramSetBytes); err != nil {
 		return encodeErrToResultBytes(result.ExecuteError, err)
 	}
 
-	subspace.SetParamSet(env.Ctx, pset)
-
 	// return empty bytes if no error
 	return encodeBytesResultBytes([]byte{})
 }
@@ -360,9 +752,13 @@ func GetValidatorAddress(envId uint64, n int32) *C.char {
 func GetValidatorPrivateKey(envId uint64, n int32) *C.char {
 	env := loadEnv(envId)
 
-	priv := env.ValPrivs[n].Key
-	base64Priv := base64.StdEncoding.EncodeToString(priv)
-	return C.CString(base64Priv)
+	// envs initialized from a genesis document do not know any validator private key
+	if n < 0 || int(n) >= len(env.ValPrivs) {
+		err := fmt.Errorf("no private key for validator %d, env has %d validator private keys", n, len(env.ValPrivs))
+		return encodeErrToResultBytes(result.QueryError, err)
+	}
+
+	return encodeBytesResultBytes(env.ValPrivs[n].Key)
 }
 
 // ========= utils =========
@@ -376,13 +772,40 @@ func loadEnv(envId uint64) testenv.TestEnv {
 	return env
 }
 
+// hasEnvs tells whether any env is in use, envs rely on the process wide sdk config.
+func hasEnvs() bool {
+	found := false
+	envRegister.Range(func(_, _ any) bool {
+		found = true
+		return false
+	})
+	return found
+}
+
 func encodeErrToResultBytes(code byte, err error) *C.char {
 	return C.CString(result.EncodeResultFromError(code, err))
 }
 
+// encodeTxErrToResultBytes encodes a tx failure the same way DeliverTx reports it.
+func encodeTxErrToResultBytes(err error, gasInfo sdk.GasInfo) *C.char {
+	resDeliverTx := sdkerrors.ResponseDeliverTx(err, gasInfo.GasWanted, gasInfo.GasUsed, false)
+	bz, err := proto.Marshal(&resDeliverTx)
+	if err != nil {
+		panic(err)
+	}
+
+	return C.CString(result.EncodeResultFromTxError(bz))
+}
+
 func encodeBytesResultBytes(bytes []byte) *C.char {
 	return C.CString(result.EncodeResultFromOk(bytes))
 }
 
+func encodeUint64ResultBytes(n uint64) *C.char {
+	bz := make([]byte, 8)
+	binary.BigEndian.PutUint64(bz, n)
+	return encodeBytesResultBytes(bz)
+}
+
 // must define main for ffi build
 func main() {}
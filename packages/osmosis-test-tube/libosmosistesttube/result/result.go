@@ -9,6 +9,8 @@ var (
 	Ok           byte = 0
 	QueryError   byte = 1
 	ExecuteError byte = 2
+	// TxError carries a proto encoded `ResponseDeliverTx` of a failed tx.
+	TxError byte = 3
 )
 
 func markError(code byte, data []byte) []byte {
@@ -24,6 +26,11 @@ func EncodeResultFromError(code byte, err error) string {
 	return base64.StdEncoding.EncodeToString(marked)
 }
 
+func EncodeResultFromTxError(resDeliverTxBytes []byte) string {
+	marked := markError(TxError, resDeliverTxBytes)
+	return base64.StdEncoding.EncodeToString(marked)
+}
+
 func EncodeResultFromOk(data []byte) string {
 	marked := markOk(data)
 	return base64.StdEncoding.EncodeToString(marked)
@@ -0,0 +1,134 @@
+package testenv
+
+import (
+	// cosmos-sdk
+	"github.com/cosmos/cosmos-sdk/simapp"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	banktypes "github.com/cosmos/cosmos-sdk/x/bank/types"
+	minttypes "github.com/cosmos/cosmos-sdk/x/mint/types"
+)
+
+// FundAccount mints `coins` and sends them to `addr`, registering denom metadata for denoms
+// the bank module does not know yet. It must be called while a block is in progress.
+func (env *TestEnv) FundAccount(addr sdk.AccAddress, coins sdk.Coins) error {
+	return env.fundAccount(env.Ctx, addr, coins)
+}
+
+// FundAccounts is FundAccount for many accounts at once, minting the coins for all of them
+// in one go. It must be called while a block is in progress.
+func (env *TestEnv) FundAccounts(addrs []sdk.AccAddress, coins sdk.Coins) error {
+	total := sdk.NewCoins()
+	for _, coin := range coins {
+		total = total.Add(sdk.NewCoin(coin.Denom, coin.Amount.MulRaw(int64(len(addrs)))))
+	}
+
+	env.registerDenoms(env.Ctx, coins)
+	if err := env.App.BankKeeper.MintCoins(env.Ctx, minttypes.ModuleName, total); err != nil {
+		return err
+	}
+
+	for _, addr := range addrs {
+		if err := env.App.BankKeeper.SendCoinsFromModuleToAccount(env.Ctx, minttypes.ModuleName, addr, coins); err != nil {
+			return err
+		}
+	}
+
+	return nil
+}
+
+// MintTo is FundAccount, but it also works between blocks, see updateState.
+func (env *TestEnv) MintTo(addr sdk.AccAddress, coins sdk.Coins) error {
+	return env.updateState(func(ctx sdk.Context) error {
+		return env.fundAccount(ctx, addr, coins)
+	})
+}
+
+// SetBalance mints or burns coins of `addr` until its balance is exactly `coins`,
+// denoms missing from `coins` are burned entirely. It works between blocks, see updateState.
+func (env *TestEnv) SetBalance(addr sdk.AccAddress, coins sdk.Coins) error {
+	return env.updateState(func(ctx sdk.Context) error {
+		balance := env.App.BankKeeper.GetAllBalances(ctx, addr)
+
+		excess := sdk.NewCoins()
+		for _, coin := range balance {
+			if target := coins.AmountOf(coin.Denom); coin.Amount.GT(target) {
+				excess = excess.Add(sdk.NewCoin(coin.Denom, coin.Amount.Sub(target)))
+			}
+		}
+
+		missing := sdk.NewCoins()
+		for _, coin := range coins {
+			if current := balance.AmountOf(coin.Denom); coin.Amount.GT(current) {
+				missing = missing.Add(sdk.NewCoin(coin.Denom, coin.Amount.Sub(current)))
+			}
+		}
+
+		if !excess.IsZero() {
+			if err := env.App.BankKeeper.SendCoinsFromAccountToModule(ctx, addr, minttypes.ModuleName, excess); err != nil {
+				return err
+			}
+			if err := env.App.BankKeeper.BurnCoins(ctx, minttypes.ModuleName, excess); err != nil {
+				return err
+			}
+		}
+
+		return env.fundAccount(ctx, addr, missing)
+	})
+}
+
+// updateState applies `update` to the block in progress or, between blocks, to the working
+// state of the app, which the next block builds on and commits. This way no block has to be
+// produced for it. State is left untouched if `update` fails.
+func (env *TestEnv) updateState(update func(ctx sdk.Context) error) error {
+	if env.BlockInProgress {
+		ctx, write := env.Ctx.CacheContext()
+		if err := update(ctx); err != nil {
+			return err
+		}
+		write()
+		return nil
+	}
+
+	ctx := env.Ctx.WithMultiStore(env.App.CommitMultiStore().CacheMultiStore())
+	if err := update(ctx); err != nil {
+		return err
+	}
+
+	// simulations run against the check state, which is only reset on commit
+	checkCtx, writeCheckState := env.App.NewContext(true, env.Ctx.BlockHeader()).CacheContext()
+	if err := update(checkCtx); err != nil {
+		return err
+	}
+
+	ctx.MultiStore().(sdk.CacheMultiStore).Write()
+	writeCheckState()
+
+	// drop reads cached by the context left behind by the last block
+	env.Ctx = env.Ctx.WithMultiStore(env.App.CommitMultiStore().CacheMultiStore())
+	env.UncommittedChanges = true
+
+	return nil
+}
+
+func (env *TestEnv) fundAccount(ctx sdk.Context, addr sdk.AccAddress, coins sdk.Coins) error {
+	env.registerDenoms(ctx, coins)
+	return simapp.FundAccount(env.App.BankKeeper, ctx, addr, coins)
+}
+
+func (env *TestEnv) registerDenoms(ctx sdk.Context, coins sdk.Coins) {
+	for _, coin := range coins {
+		// create denom if not exist
+		_, hasDenomMetaData := env.App.BankKeeper.GetDenomMetaData(ctx, coin.Denom)
+		if !hasDenomMetaData {
+			denomMetaData := banktypes.Metadata{
+				DenomUnits: []*banktypes.DenomUnit{{
+					Denom:    coin.Denom,
+					Exponent: 0,
+				}},
+				Base: coin.Denom,
+			}
+
+			env.App.BankKeeper.SetDenomMetaData(ctx, denomMetaData)
+		}
+	}
+}
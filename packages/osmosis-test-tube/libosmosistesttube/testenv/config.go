@@ -0,0 +1,159 @@
+package testenv
+
+import (
+	"encoding/json"
+	"fmt"
+	"time"
+
+	// cosmos-sdk
+	codectypes "github.com/cosmos/cosmos-sdk/codec/types"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	proto "github.com/gogo/protobuf/proto"
+)
+
+// Config customizes the chain set up by InitTestEnv.
+type Config struct {
+	ChainID string `json:"chain_id"`
+	// FeeDenom replaces the sdk default denom in genesis and funds validators.
+	FeeDenom     string `json:"fee_denom"`
+	Bech32Prefix string `json:"bech32_prefix"`
+	// GenesisTime in unix nanoseconds, see ParseConfig for the default.
+	GenesisTime *uint64 `json:"genesis_time,omitempty,string"`
+	// AppState holds per module overrides, deep merged into the default genesis state.
+	AppState map[string]json.RawMessage `json:"app_state,omitempty"`
+	// ParamSets are set right after InitChain, before the first block.
+	ParamSets []ParamSetConfig `json:"param_sets,omitempty"`
+	// Validators making up the initial validator set, a single validator is created if empty.
+	Validators []ValidatorConfig `json:"validators,omitempty"`
+}
+
+type ParamSetConfig struct {
+	Subspace string `json:"subspace"`
+	// Value is a proto encoded `Any` of the param set.
+	Value []byte `json:"value"`
+}
+
+type ValidatorConfig struct {
+	SelfBond sdk.Int `json:"self_bond"`
+}
+
+// ParseConfig decodes a JSON config, defaulting genesis time to now.
+func ParseConfig(configJson []byte) (Config, error) {
+	var cfg Config
+	if err := json.Unmarshal(configJson, &cfg); err != nil {
+		return Config{}, err
+	}
+
+	if cfg.GenesisTime == nil {
+		now := uint64(time.Now().UnixNano())
+		cfg.GenesisTime = &now
+	}
+
+	return cfg, nil
+}
+
+// SetBech32Prefixes sets the bech32 prefixes of the sdk config. The sdk config is process global,
+// so envs with different prefixes can't be used at the same time: changing the prefix fails
+// while other envs are in use.
+func (cfg Config) SetBech32Prefixes(envsInUse bool) error {
+	prefix := cfg.Bech32Prefix
+	config := sdk.GetConfig()
+	current := config.GetBech32AccountAddrPrefix()
+	if current == prefix {
+		return nil
+	}
+	if envsInUse {
+		return fmt.Errorf("bech32 prefix `%s` differs from prefix `%s` of the envs in use, the prefix is process wide", prefix, current)
+	}
+
+	config.SetBech32PrefixForAccount(prefix, prefix+sdk.PrefixPublic)
+	config.SetBech32PrefixForValidator(prefix+sdk.PrefixValidator+sdk.PrefixOperator, prefix+sdk.PrefixValidator+sdk.PrefixOperator+sdk.PrefixPublic)
+	config.SetBech32PrefixForConsensusNode(prefix+sdk.PrefixValidator+sdk.PrefixConsensus, prefix+sdk.PrefixValidator+sdk.PrefixConsensus+sdk.PrefixPublic)
+
+	return nil
+}
+
+func (cfg Config) GenesisTimeUTC() time.Time {
+	return time.Unix(0, int64(*cfg.GenesisTime)).UTC()
+}
+
+// applyAppState merges the app state overrides of the config into the genesis state.
+func (cfg Config) applyAppState(genesisState map[string]json.RawMessage) error {
+	for module, override := range cfg.AppState {
+		state, ok := genesisState[module]
+		if !ok {
+			return fmt.Errorf("no genesis state found for module `%s`", module)
+		}
+
+		var base, patch interface{}
+		if err := json.Unmarshal(state, &base); err != nil {
+			return err
+		}
+		if err := json.Unmarshal(override, &patch); err != nil {
+			return fmt.Errorf("invalid genesis state override for module `%s`: %w", module, err)
+		}
+
+		merged, err := json.Marshal(mergeJSON(base, patch))
+		if err != nil {
+			return err
+		}
+		genesisState[module] = merged
+	}
+
+	return nil
+}
+
+// mergeJSON recursively merges objects, any other value in patch replaces the one in base.
+func mergeJSON(base, patch interface{}) interface{} {
+	baseObj, ok := base.(map[string]interface{})
+	if !ok {
+		return patch
+	}
+	patchObj, ok := patch.(map[string]interface{})
+	if !ok {
+		return patch
+	}
+
+	for k, v := range patchObj {
+		baseObj[k] = mergeJSON(baseObj[k], v)
+	}
+	return baseObj
+}
+
+// ApplyConfig sets up the param sets and validators of the config. It must be called
+// before the first block begins.
+func (env *TestEnv) ApplyConfig(cfg Config) error {
+	for _, p := range cfg.ParamSets {
+		if err := env.SetParamSet(p.Subspace, p.Value); err != nil {
+			return err
+		}
+	}
+
+	for _, v := range cfg.Validators {
+		env.SetupValidator(v.SelfBond)
+	}
+
+	return nil
+}
+
+// SetParamSet sets the param set of a subspace from a proto encoded `Any`.
+func (env *TestEnv) SetParamSet(subspaceName string, paramSetBytes []byte) error {
+	subspace, ok := env.App.ParamsKeeper.GetSubspace(subspaceName)
+	if !ok {
+		return fmt.Errorf("No subspace found for `%s`", subspaceName)
+	}
+
+	any := codectypes.Any{}
+	if err := proto.Unmarshal(paramSetBytes, &any); err != nil {
+		return err
+	}
+
+	pset, err := env.ParamTypesRegistry.UnpackAny(&any)
+	if err != nil {
+		return err
+	}
+
+	subspace.SetParamSet(env.Ctx, pset)
+
+	return nil
+}
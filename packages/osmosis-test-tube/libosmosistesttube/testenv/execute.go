@@ -0,0 +1,132 @@
+package testenv
+
+import (
+	// tendermint
+	abci "github.com/tendermint/tendermint/abci/types"
+
+	// cosmos-sdk
+	sdk "github.com/cosmos/cosmos-sdk/types"
+	sdkerrors "github.com/cosmos/cosmos-sdk/types/errors"
+	txtypes "github.com/cosmos/cosmos-sdk/types/tx"
+
+	// wasmd
+	wasmtypes "github.com/CosmWasm/wasmd/x/wasm/types"
+
+	// helpers
+	proto "github.com/gogo/protobuf/proto"
+)
+
+// ExecuteAs executes the messages of a proto encoded `TxBody` with `sender` as their only signer.
+// No tx is built, so the whole ante handler is skipped: signatures, fees, sequences,
+// account existence and gas limits are not checked.
+// It must be called while a block is in progress.
+func (env *TestEnv) ExecuteAs(sender sdk.AccAddress, txBodyBytes []byte) (abci.ResponseDeliverTx, error) {
+	var body txtypes.TxBody
+	if err := env.App.AppCodec().Unmarshal(txBodyBytes, &body); err != nil {
+		return abci.ResponseDeliverTx{}, sdkerrors.Wrap(sdkerrors.ErrTxDecode, err.Error())
+	}
+
+	msgs := body.GetMsgs()
+	for i, msg := range msgs {
+		signers := msg.GetSigners()
+		if len(signers) != 1 || !signers[0].Equals(sender) {
+			return abci.ResponseDeliverTx{}, sdkerrors.Wrapf(sdkerrors.ErrUnauthorized, "message index: %d: signers %v, expected `%s`", i, signers, sender)
+		}
+	}
+
+	return env.executeMsgs(msgs)
+}
+
+// ExecuteAsContract is ExecuteAs for a contract sender, the way the wasm module dispatches
+// messages returned by a contract.
+func (env *TestEnv) ExecuteAsContract(contract sdk.AccAddress, txBodyBytes []byte) (abci.ResponseDeliverTx, error) {
+	if !env.App.WasmKeeper.HasContractInfo(env.Ctx, contract) {
+		return abci.ResponseDeliverTx{}, sdkerrors.Wrapf(sdkerrors.ErrInvalidAddress, "`%s` is not a contract", contract)
+	}
+
+	return env.ExecuteAs(contract, txBodyBytes)
+}
+
+// Sudo calls the sudo entry point of a contract, as other modules of the chain do.
+// The data set by the contract is returned as the data of a `MsgExecuteContractResponse`,
+// which is encoded the same way as `MsgSudoContractResponse` of later wasmd versions.
+// It must be called while a block is in progress.
+func (env *TestEnv) Sudo(contract sdk.AccAddress, msg []byte) (abci.ResponseDeliverTx, error) {
+	ctx, write := env.Ctx.
+		WithGasMeter(sdk.NewInfiniteGasMeter()).
+		WithEventManager(sdk.NewEventManager()).
+		CacheContext()
+
+	data, err := env.App.WasmKeeper.Sudo(ctx, contract, msg)
+	gasUsed := int64(ctx.GasMeter().GasConsumed())
+	if err != nil {
+		return abci.ResponseDeliverTx{GasUsed: gasUsed}, err
+	}
+
+	res, err := proto.Marshal(&wasmtypes.MsgExecuteContractResponse{Data: data})
+	if err != nil {
+		return abci.ResponseDeliverTx{GasUsed: gasUsed}, err
+	}
+
+	msgData, err := proto.Marshal(&sdk.TxMsgData{Data: []*sdk.MsgData{{MsgType: "sudo", Data: res}}})
+	if err != nil {
+		return abci.ResponseDeliverTx{GasUsed: gasUsed}, err
+	}
+
+	write()
+
+	return abci.ResponseDeliverTx{
+		Data:    msgData,
+		GasUsed: gasUsed,
+		Events:  ctx.EventManager().ABCIEvents(),
+	}, nil
+}
+
+// executeMsgs runs messages against the current block, skipping the ante handler.
+// State is only written if all of them succeed, mirroring `runMsgs` of the base app.
+func (env *TestEnv) executeMsgs(msgs []sdk.Msg) (abci.ResponseDeliverTx, error) {
+	ctx, write := env.Ctx.
+		WithGasMeter(sdk.NewInfiniteGasMeter()).
+		WithEventManager(sdk.NewEventManager()).
+		CacheContext()
+
+	gasUsed := func() int64 { return int64(ctx.GasMeter().GasConsumed()) }
+
+	msgData := make([]*sdk.MsgData, 0, len(msgs))
+	events := sdk.EmptyEvents()
+
+	for i, msg := range msgs {
+		if err := msg.ValidateBasic(); err != nil {
+			return abci.ResponseDeliverTx{GasUsed: gasUsed()}, sdkerrors.Wrapf(err, "failed to execute message; message index: %d", i)
+		}
+
+		handler := env.App.MsgServiceRouter().Handler(msg)
+		if handler == nil {
+			return abci.ResponseDeliverTx{GasUsed: gasUsed()}, sdkerrors.Wrapf(sdkerrors.ErrUnknownRequest, "failed to execute message; message index: %d: unrecognized message route: %s", i, sdk.MsgTypeURL(msg))
+		}
+
+		msgResult, err := handler(ctx, msg)
+		if err != nil {
+			return abci.ResponseDeliverTx{GasUsed: gasUsed()}, sdkerrors.Wrapf(err, "failed to execute message; message index: %d", i)
+		}
+
+		msgEvents := sdk.Events{
+			sdk.NewEvent(sdk.EventTypeMessage, sdk.NewAttribute(sdk.AttributeKeyAction, sdk.MsgTypeURL(msg))),
+		}
+		events = events.AppendEvents(msgEvents.AppendEvents(msgResult.GetEvents()))
+		msgData = append(msgData, &sdk.MsgData{MsgType: sdk.MsgTypeURL(msg), Data: msgResult.Data})
+	}
+
+	data, err := proto.Marshal(&sdk.TxMsgData{Data: msgData})
+	if err != nil {
+		return abci.ResponseDeliverTx{GasUsed: gasUsed()}, err
+	}
+
+	write()
+
+	return abci.ResponseDeliverTx{
+		Data:    data,
+		GasUsed: gasUsed(),
+		Events:  events.ToABCIEvents(),
+	}, nil
+}
@@ -0,0 +1,86 @@
+package testenv
+
+import (
+	"fmt"
+
+	// tendermint
+	abci "github.com/tendermint/tendermint/abci/types"
+	tmjson "github.com/tendermint/tendermint/libs/json"
+	tmproto "github.com/tendermint/tendermint/proto/tendermint/types"
+	tmtypes "github.com/tendermint/tendermint/types"
+	dbm "github.com/tendermint/tm-db"
+)
+
+// ExportGenesis exports the committed state of the env as a genesis document.
+// A chain booted from it continues at the height following the last committed block.
+//
+// Validators are left out of the document, the validator set is derived from
+// the staking state in `app_state` when the genesis is imported.
+func (env *TestEnv) ExportGenesis() ([]byte, error) {
+	exported, err := env.App.ExportAppStateAndValidators(false, nil, nil)
+	if err != nil {
+		return nil, err
+	}
+
+	consensusParams := tmtypes.UpdateConsensusParams(*tmtypes.DefaultConsensusParams(), exported.ConsensusParams)
+
+	doc := tmtypes.GenesisDoc{
+		GenesisTime:     env.Ctx.BlockTime(),
+		ChainID:         env.Ctx.ChainID(),
+		InitialHeight:   exported.Height,
+		ConsensusParams: &consensusParams,
+		AppState:        exported.AppState,
+	}
+
+	return tmjson.MarshalIndent(doc, "", "  ")
+}
+
+// NewTestEnvFromGenesis boots a new TestEnv from a genesis document, initializing the chain
+// at the document's initial height. No block is produced, so the env is left right before
+// its first block.
+//
+// Only the fee denom and bech32 prefix of the config are used, the chain id is taken from
+// the genesis document. The bech32 prefix must be set beforehand with SetBech32Prefixes. Validator private keys are not part of genesis, so `ValPrivs` is empty.
+func NewTestEnvFromGenesis(nodeHome string, cfg Config, genesisJson []byte) (env *TestEnv, err error) {
+	doc, err := tmtypes.GenesisDocFromJSON(genesisJson)
+	if err != nil {
+		return nil, err
+	}
+
+	cfg.ChainID = doc.ChainID
+
+	db := dbm.NewMemDB()
+
+	env = new(TestEnv)
+	env.App = newOsmosisApp(nodeHome, db)
+	env.DB = db
+	env.NodeHome = nodeHome
+	env.ParamTypesRegistry = *NewParamTypeRegistry()
+	env.Config = cfg
+	env.BlockInterval = DefaultBlockInterval
+
+	env.SetupParamTypes()
+
+	// modules panic on invalid genesis state
+	defer func() {
+		if r := recover(); r != nil {
+			env = nil
+			err = fmt.Errorf("failed to init chain from genesis: %v", r)
+		}
+	}()
+
+	env.App.InitChain(
+		abci.RequestInitChain{
+			Time:            doc.GenesisTime,
+			ChainId:         doc.ChainID,
+			ConsensusParams: tmtypes.TM2PB.ConsensusParams(doc.ConsensusParams),
+			Validators:      []abci.ValidatorUpdate{},
+			AppStateBytes:   doc.AppState,
+			InitialHeight:   doc.InitialHeight,
+		},
+	)
+
+	env.Ctx = env.App.BaseApp.NewContext(false, tmproto.Header{Height: doc.InitialHeight - 1, ChainID: doc.ChainID, Time: doc.GenesisTime})
+
+	return env, nil
+}
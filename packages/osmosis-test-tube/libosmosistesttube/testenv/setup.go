@@ -47,12 +47,25 @@ import (
 
 type TestEnv struct {
 	App                *app.OsmosisApp
+	DB                 dbm.DB
 	Ctx                sdk.Context
 	ParamTypesRegistry ParamTypeRegistry
 	ValPrivs           []*secp256k1.PrivKey
 	NodeHome           string
+	Config             Config
+	// BlockInProgress is set between BeginBlock and EndBlock.
+	BlockInProgress bool
+	// BlockInterval is the time between blocks begun with BeginNextBlock.
+	BlockInterval time.Duration
+	// NextBlockTime overrides BlockInterval for the next block only.
+	NextBlockTime *time.Time
+	// UncommittedChanges is set when state is changed between blocks,
+	// those changes are committed along with the next block.
+	UncommittedChanges bool
 }
 
+const DefaultBlockInterval = 5 * time.Second
+
 // DebugAppOptions is a stub implementing AppOptions
 type DebugAppOptions struct{}
 
@@ -64,10 +77,9 @@ func (ao DebugAppOptions) Get(o string) interface{} {
 	return nil
 }
 
-func SetupOsmosisApp(nodeHome string) *app.OsmosisApp {
-	db := dbm.NewMemDB()
-
-	appInstance := app.NewOsmosisApp(
+// newOsmosisApp creates an app backed by `db`, loading the latest version stored in it.
+func newOsmosisApp(nodeHome string, db dbm.DB) *app.OsmosisApp {
+	return app.NewOsmosisApp(
 		log.NewNopLogger(),
 		db,
 		nil,
@@ -78,6 +90,10 @@ func SetupOsmosisApp(nodeHome string) *app.OsmosisApp {
 		DebugAppOptions{},
 		app.EmptyWasmOpts,
 	)
+}
+
+func SetupOsmosisApp(nodeHome string, db dbm.DB, cfg Config) (appInstance *app.OsmosisApp, err error) {
+	appInstance = newOsmosisApp(nodeHome, db)
 
 	encCfg := app.MakeEncodingConfig()
 	genesisState := app.NewDefaultGenesisState()
@@ -116,13 +132,22 @@ func SetupOsmosisApp(nodeHome string) *app.OsmosisApp {
 
 	// Set up pool incentives genesis state
 	poolIncentivesParams := poolincentivetypes.DefaultParams()
-	poolIncentivesParams.MintedDenom = "uosmo"
+	poolIncentivesParams.MintedDenom = cfg.FeeDenom
 	poolIncentivesGen := poolincentivetypes.GenesisState{
 		Params:            poolIncentivesParams,
 		LockableDurations: lockableDurations,
 	}
 	genesisState[poolincentivetypes.ModuleName] = encCfg.Marshaler.MustMarshalJSON(&poolIncentivesGen)
 
+	// replace sdk.DefaultDenom with the fee denom, a bit of a hack, needs improvement
+	for module, state := range genesisState {
+		genesisState[module] = []byte(strings.Replace(string(state), "\"stake\"", "\""+cfg.FeeDenom+"\"", -1))
+	}
+
+	if err := cfg.applyAppState(genesisState); err != nil {
+		return nil, err
+	}
+
 	stateBytes, err := json.MarshalIndent(genesisState, "", " ")
 
 	requireNoErr(err)
@@ -133,21 +158,60 @@ func SetupOsmosisApp(nodeHome string) *app.OsmosisApp {
 		MaxGas:   -1,
 	}
 
-	// replace sdk.DefaultDenom with "uosmo", a bit of a hack, needs improvement
-	stateBytes = []byte(strings.Replace(string(stateBytes), "\"stake\"", "\"uosmo\"", -1))
+	// modules panic on invalid genesis state
+	defer func() {
+		if r := recover(); r != nil {
+			appInstance = nil
+			err = fmt.Errorf("failed to init chain: %v", r)
+		}
+	}()
 
 	appInstance.InitChain(
 		abci.RequestInitChain{
+			Time:            cfg.GenesisTimeUTC(),
+			ChainId:         cfg.ChainID,
 			Validators:      []abci.ValidatorUpdate{},
 			ConsensusParams: concensusParams,
 			AppStateBytes:   stateBytes,
 		},
 	)
 
-	return appInstance
+	return appInstance, nil
 }
 
-func (env *TestEnv) BeginNewBlock(executeNextEpoch bool, timeIncreaseSeconds uint64) {
+// BeginNextBlock begins a new block at `NextBlockTime` if set, otherwise `BlockInterval` after the current one.
+func (env *TestEnv) BeginNextBlock() {
+	timeIncrease := env.BlockInterval
+	if env.NextBlockTime != nil {
+		timeIncrease = env.NextBlockTime.Sub(env.Ctx.BlockTime())
+		env.NextBlockTime = nil
+	}
+
+	env.BeginNewBlock(false, timeIncrease)
+}
+
+// NextEpochStartTime returns the earliest block time at which the next epoch of `identifier` starts,
+// but no earlier than `BlockInterval` after the current block.
+func (env *TestEnv) NextEpochStartTime(identifier string) (time.Time, error) {
+	epoch := env.App.EpochsKeeper.GetEpochInfo(env.Ctx, identifier)
+	if epoch.Identifier == "" {
+		return time.Time{}, fmt.Errorf("epoch `%s` not found", identifier)
+	}
+
+	// epochs start once block time is past the end of the current one
+	startTime := epoch.CurrentEpochStartTime.Add(epoch.Duration).Add(time.Nanosecond)
+	if !epoch.EpochCountingStarted {
+		startTime = epoch.StartTime
+	}
+
+	if !startTime.After(env.Ctx.BlockTime()) {
+		startTime = env.Ctx.BlockTime().Add(env.BlockInterval)
+	}
+
+	return startTime, nil
+}
+
+func (env *TestEnv) BeginNewBlock(executeNextEpoch bool, timeIncrease time.Duration) {
 	var valAddr []byte
 
 	validators := env.App.StakingKeeper.GetAllValidators(env.Ctx)
@@ -156,19 +220,13 @@ func (env *TestEnv) BeginNewBlock(executeNextEpoch bool, timeIncreaseSeconds uin
 		requireNoErr(err)
 		valAddr = valAddrFancy.Bytes()
 	} else {
-		valPriv, valAddrFancy := env.setupValidator(stakingtypes.Bonded)
+		valAddrFancy := env.SetupValidator(sdk.NewInt(100))
 		validator, _ := env.App.StakingKeeper.GetValidator(env.Ctx, valAddrFancy)
 		valAddr2, _ := validator.GetConsAddr()
 		valAddr = valAddr2.Bytes()
-
-		env.ValPrivs = append(env.ValPrivs, valPriv)
-		err := simapp.FundAccount(env.App.BankKeeper, env.Ctx, valAddrFancy.Bytes(), sdk.NewCoins(sdk.NewInt64Coin("uosmo", 9223372036854775807)))
-		if err != nil {
-			panic(errors.Wrapf(err, "Failed to fund account"))
-		}
 	}
 
-	env.beginNewBlockWithProposer(executeNextEpoch, valAddr, timeIncreaseSeconds)
+	env.beginNewBlockWithProposer(executeNextEpoch, valAddr, timeIncrease)
 }
 
 func (env *TestEnv) GetValidatorAddresses() []string {
@@ -182,7 +240,7 @@ func (env *TestEnv) GetValidatorAddresses() []string {
 }
 
 // beginNewBlockWithProposer begins a new block with a proposer.
-func (env *TestEnv) beginNewBlockWithProposer(executeNextEpoch bool, proposer sdk.ValAddress, timeIncreaseSeconds uint64) {
+func (env *TestEnv) beginNewBlockWithProposer(executeNextEpoch bool, proposer sdk.ValAddress, timeIncrease time.Duration) {
 	validator, found := env.App.StakingKeeper.GetValidator(env.Ctx, proposer)
 
 	if !found {
@@ -196,12 +254,12 @@ func (env *TestEnv) beginNewBlockWithProposer(executeNextEpoch bool, proposer sd
 
 	epochIdentifier := env.App.SuperfluidKeeper.GetEpochIdentifier(env.Ctx)
 	epoch := env.App.EpochsKeeper.GetEpochInfo(env.Ctx, epochIdentifier)
-	newBlockTime := env.Ctx.BlockTime().Add(time.Duration(timeIncreaseSeconds) * time.Second)
+	newBlockTime := env.Ctx.BlockTime().Add(timeIncrease)
 	if executeNextEpoch {
 		newBlockTime = env.Ctx.BlockTime().Add(epoch.Duration).Add(time.Second)
 	}
 
-	header := tmtypes.Header{ChainID: "osmosis-1", Height: env.Ctx.BlockHeight() + 1, Time: newBlockTime}
+	header := tmtypes.Header{ChainID: env.Ctx.ChainID(), Height: env.Ctx.BlockHeight() + 1, Time: newBlockTime}
 	newCtx := env.Ctx.WithBlockTime(newBlockTime).WithBlockHeight(env.Ctx.BlockHeight() + 1)
 	env.Ctx = newCtx
 	lastCommitInfo := abci.LastCommitInfo{
@@ -216,12 +274,26 @@ func (env *TestEnv) beginNewBlockWithProposer(executeNextEpoch bool, proposer sd
 	env.Ctx = env.App.NewContext(false, reqBeginBlock.Header)
 }
 
-func (env *TestEnv) setupValidator(bondStatus stakingtypes.BondStatus) (*secp256k1.PrivKey, sdk.ValAddress) {
+// SetupValidator creates a bonded validator with the given self bond and a funded account
+// to sign with. Its private key is appended to `ValPrivs`.
+func (env *TestEnv) SetupValidator(selfBondAmount sdk.Int) sdk.ValAddress {
+	valPriv, valAddr := env.setupValidator(stakingtypes.Bonded, selfBondAmount)
+
+	env.ValPrivs = append(env.ValPrivs, valPriv)
+	err := simapp.FundAccount(env.App.BankKeeper, env.Ctx, valAddr.Bytes(), sdk.NewCoins(sdk.NewInt64Coin(env.Config.FeeDenom, 9223372036854775807)))
+	if err != nil {
+		panic(errors.Wrapf(err, "Failed to fund account"))
+	}
+
+	return valAddr
+}
+
+func (env *TestEnv) setupValidator(bondStatus stakingtypes.BondStatus, selfBondAmount sdk.Int) (*secp256k1.PrivKey, sdk.ValAddress) {
 	valPriv := secp256k1.GenPrivKey()
 	valPub := valPriv.PubKey()
 	valAddr := sdk.ValAddress(valPub.Address())
 	bondDenom := env.App.StakingKeeper.GetParams(env.Ctx).BondDenom
-	selfBond := sdk.NewCoins(sdk.Coin{Amount: sdk.NewInt(100), Denom: bondDenom})
+	selfBond := sdk.NewCoins(sdk.Coin{Amount: selfBondAmount, Denom: bondDenom})
 
 	err := simapp.FundAccount(env.App.BankKeeper, env.Ctx, sdk.AccAddress(valPub.Address()), selfBond)
 	requireNoErr(err)
@@ -0,0 +1,198 @@
+package testenv
+
+import (
+	"errors"
+	"io"
+	"os"
+	"path/filepath"
+
+	// tendermint
+	tmproto "github.com/tendermint/tendermint/proto/tendermint/types"
+	dbm "github.com/tendermint/tm-db"
+
+	// cosmos-sdk
+	"github.com/cosmos/cosmos-sdk/crypto/keys/secp256k1"
+	sdk "github.com/cosmos/cosmos-sdk/types"
+)
+
+// Snapshot holds a copy of the committed state of a TestEnv, including its node home
+// (where wasm code and compiled modules live), so that it can be turned back into
+// a TestEnv any number of times.
+type Snapshot struct {
+	db       dbm.DB
+	nodeHome string
+	header   tmproto.Header
+	valPrivs []*secp256k1.PrivKey
+	config   Config
+}
+
+// TakeSnapshot copies the committed state of the env. It must be called between blocks,
+// since state of a block in progress is not committed yet, and not right after state
+// was changed between blocks, see UncommittedChanges.
+func (env *TestEnv) TakeSnapshot() (*Snapshot, error) {
+	if env.BlockInProgress {
+		return nil, errors.New("cannot take snapshot while a block is in progress")
+	}
+	if env.UncommittedChanges {
+		return nil, errors.New("cannot take snapshot with state changes not committed by a block yet")
+	}
+
+	nodeHome, err := os.MkdirTemp("", ".osmosis-test-tube-snapshot-")
+	if err != nil {
+		return nil, err
+	}
+
+	if err := copyDir(env.NodeHome, nodeHome); err != nil {
+		os.RemoveAll(nodeHome)
+		return nil, err
+	}
+
+	db, err := copyDB(env.DB)
+	if err != nil {
+		os.RemoveAll(nodeHome)
+		return nil, err
+	}
+
+	return &Snapshot{
+		db:       db,
+		nodeHome: nodeHome,
+		header:   env.Ctx.BlockHeader(),
+		valPrivs: append([]*secp256k1.PrivKey{}, env.ValPrivs...),
+		config:   env.Config,
+	}, nil
+}
+
+// NewTestEnv boots a new TestEnv from the snapshot. The env gets its own node home and
+// database, so it is fully independent from the snapshot and from any other env.
+func (s *Snapshot) NewTestEnv() (*TestEnv, error) {
+	return newTestEnv(s.nodeHome, s.db, s.header, s.valPrivs, s.config)
+}
+
+// Fork creates a new TestEnv from the committed state of the env. Like snapshots,
+// it must be called between blocks. The forked env shares nothing with the original one.
+func (env *TestEnv) Fork() (*TestEnv, error) {
+	if env.BlockInProgress {
+		return nil, errors.New("cannot fork while a block is in progress")
+	}
+	if env.UncommittedChanges {
+		return nil, errors.New("cannot fork with state changes not committed by a block yet")
+	}
+
+	forked, err := newTestEnv(env.NodeHome, env.DB, env.Ctx.BlockHeader(), env.ValPrivs, env.Config)
+	if err != nil {
+		return nil, err
+	}
+
+	forked.BlockInterval = env.BlockInterval
+	forked.NextBlockTime = env.NextBlockTime
+
+	return forked, nil
+}
+
+// CleanUp releases the database and removes the files held by the snapshot.
+func (s *Snapshot) CleanUp() error {
+	if err := s.db.Close(); err != nil {
+		return err
+	}
+	return os.RemoveAll(s.nodeHome)
+}
+
+// Close releases the database and removes the node home of the env, for envs that are
+// cleaned up or replaced by a restored snapshot. The env must not be used afterwards.
+func (env *TestEnv) Close() error {
+	if err := env.DB.Close(); err != nil {
+		return err
+	}
+	return os.RemoveAll(env.NodeHome)
+}
+
+func newTestEnv(srcNodeHome string, srcDB dbm.DB, header tmproto.Header, valPrivs []*secp256k1.PrivKey, config Config) (*TestEnv, error) {
+	nodeHome, err := os.MkdirTemp("", ".osmosis-test-tube-temp-")
+	if err != nil {
+		return nil, err
+	}
+
+	if err := copyDir(srcNodeHome, nodeHome); err != nil {
+		os.RemoveAll(nodeHome)
+		return nil, err
+	}
+
+	db, err := copyDB(srcDB)
+	if err != nil {
+		os.RemoveAll(nodeHome)
+		return nil, err
+	}
+
+	env := new(TestEnv)
+	env.App = newOsmosisApp(nodeHome, db)
+	env.DB = db
+	env.NodeHome = nodeHome
+	env.ParamTypesRegistry = *NewParamTypeRegistry()
+	env.ValPrivs = append([]*secp256k1.PrivKey{}, valPrivs...)
+	env.Config = config
+	env.BlockInterval = DefaultBlockInterval
+
+	env.SetupParamTypes()
+
+	// same as the context left behind after a block is committed
+	env.Ctx = sdk.NewContext(env.App.CommitMultiStore().CacheMultiStore(), header, false, env.App.Logger())
+
+	return env, nil
+}
+
+func copyDB(src dbm.DB) (dbm.DB, error) {
+	dst := dbm.NewMemDB()
+
+	itr, err := src.Iterator(nil, nil)
+	if err != nil {
+		return nil, err
+	}
+	defer itr.Close()
+
+	for ; itr.Valid(); itr.Next() {
+		key := append([]byte{}, itr.Key()...)
+		value := append([]byte{}, itr.Value()...)
+		if err := dst.Set(key, value); err != nil {
+			return nil, err
+		}
+	}
+
+	return dst, itr.Error()
+}
+
+func copyDir(src, dst string) error {
+	return filepath.Walk(src, func(path string, info os.FileInfo, err error) error {
+		if err != nil {
+			return err
+		}
+
+		rel, err := filepath.Rel(src, path)
+		if err != nil {
+			return err
+		}
+		target := filepath.Join(dst, rel)
+
+		if info.IsDir() {
+			return os.MkdirAll(target, info.Mode())
+		}
+
+		return copyFile(path, target, info.Mode())
+	})
+}
+
+func copyFile(src, dst string, mode os.FileMode) error {
+	in, err := os.Open(src)
+	if err != nil {
+		return err
+	}
+	defer in.Close()
+
+	out, err := os.OpenFile(dst, os.O_CREATE|os.O_WRONLY|os.O_TRUNC, mode)
+	if err != nil {
+		return err
+	}
+	defer out.Close()
+
+	_, err = io.Copy(out, in)
+	return err
+}
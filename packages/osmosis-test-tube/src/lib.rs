@@ -1,4 +1,7 @@
 #![doc = include_str!("../README.md")]
+// `RunnerError` holds tendermint rpc errors by value and is returned everywhere,
+// boxing them would break matching on its variants
+#![allow(clippy::result_large_err)]
 
 mod module;
 mod runner;
@@ -8,8 +11,14 @@ pub use osmosis_std;
 
 pub use module::*;
 pub use runner::app::OsmosisTestApp;
-pub use test_tube::account::{Account, FeeSetting, NonSigningAccount, SigningAccount};
-pub use test_tube::runner::error::{DecodeError, EncodeError, RunnerError};
+pub use runner::builder::OsmosisTestAppBuilder;
+pub use test_tube::account::{
+    Account, FeeSetting, MultisigAccount, NonSigningAccount, SigningAccount,
+};
+pub use test_tube::runner::app::SnapshotId;
+pub use test_tube::runner::error::{
+    DecodeError, EncodeError, RunnerError, TxError, WasmContractError,
+};
 pub use test_tube::runner::result::{ExecuteResponse, RunnerExecuteResult, RunnerResult};
 pub use test_tube::runner::Runner;
-pub use test_tube::{fn_execute, fn_query};
+pub use test_tube::{fn_execute, fn_query, sdkerrors};
@@ -7,15 +7,17 @@ mod twap;
 
 pub use test_tube::macros;
 pub use test_tube::module::bank;
+pub use test_tube::module::feegrant;
 pub use test_tube::module::wasm;
 pub use test_tube::module::Module;
 
 pub use bank::Bank;
 pub use concentrated_liquidity::ConcentratedLiquidity;
+pub use feegrant::Feegrant;
 pub use gamm::Gamm;
 pub use gov::Gov;
 pub use gov::GovWithAppAccess;
 pub use pool_manager::PoolManager;
 pub use tokenfactory::TokenFactory;
 pub use twap::Twap;
-pub use wasm::Wasm;
+pub use wasm::{Contract, Wasm};
@@ -1,19 +1,23 @@
+use std::path::Path;
+use std::time::Duration;
+
 use cosmrs::Any;
 
 use cosmwasm_std::{Coin, Timestamp};
 
 use prost::Message;
 use serde::de::DeserializeOwned;
-use test_tube::account::SigningAccount;
+use test_tube::account::{MultisigAccount, SigningAccount};
+use test_tube::module::wasm::StoredCodes;
 
 use test_tube::runner::result::{RunnerExecuteResult, RunnerResult};
 use test_tube::runner::Runner;
-use test_tube::BaseApp;
+use test_tube::{BaseApp, SnapshotId};
 
-const FEE_DENOM: &str = "uosmo";
-const OSMO_ADDRESS_PREFIX: &str = "osmo";
-const CHAIN_ID: &str = "osmosis-1";
-const DEFAULT_GAS_ADJUSTMENT: f64 = 1.2;
+pub(crate) const FEE_DENOM: &str = "uosmo";
+pub(crate) const OSMO_ADDRESS_PREFIX: &str = "osmo";
+pub(crate) const CHAIN_ID: &str = "osmosis-1";
+pub(crate) const DEFAULT_GAS_ADJUSTMENT: f64 = 1.2;
 
 #[derive(Debug, PartialEq)]
 pub struct OsmosisTestApp {
@@ -38,6 +42,105 @@ impl OsmosisTestApp {
         }
     }
 
+    pub(crate) fn from_base_app(inner: BaseApp) -> Self {
+        Self { inner }
+    }
+
+    /// Boot a new app from a genesis JSON file, such as one written by `export_genesis`
+    /// or a trimmed mainnet export.
+    ///
+    /// Validator private keys are not part of genesis, so `get_first_validator_signing_account`
+    /// returns an error on an app booted this way.
+    pub fn from_genesis(path: impl AsRef<Path>) -> RunnerResult<Self> {
+        Ok(Self {
+            inner: BaseApp::from_genesis(
+                path,
+                FEE_DENOM,
+                OSMO_ADDRESS_PREFIX,
+                DEFAULT_GAS_ADJUSTMENT,
+            )?,
+        })
+    }
+
+    /// Export the committed chain state as a genesis JSON file.
+    /// An app booted from it with `from_genesis` continues from the next block height.
+    pub fn export_genesis(&self, path: impl AsRef<Path>) -> RunnerResult<()> {
+        self.inner.export_genesis(path)
+    }
+
+    /// Produce `n` empty blocks, each one block interval after the previous one.
+    pub fn skip_blocks(&self, n: u64) -> RunnerResult<()> {
+        self.inner.skip_blocks(n)
+    }
+
+    /// Produce a single block at the time the next epoch with the given identifier,
+    /// e.g. `"day"` or `"week"`, starts so that its hooks run: incentives distribution,
+    /// superfluid, mint and so on.
+    pub fn advance_to_next_epoch(&self, identifier: &str) -> RunnerResult<()> {
+        self.inner.advance_to_next_epoch(identifier)
+    }
+
+    /// Set the time between blocks, 5 seconds by default.
+    pub fn set_block_interval(&self, interval: Duration) {
+        self.inner.set_block_interval(interval)
+    }
+
+    /// Set the time of the next block, e.g. the one including the next tx,
+    /// instead of advancing by the block interval. `time` must be after the current block time.
+    /// `increase_time` discards it.
+    pub fn set_block_time(&self, time: Timestamp) -> RunnerResult<()> {
+        self.inner.set_block_time(time)
+    }
+
+    /// Begin a new block. Until `end_block_and_commit` is called, every tx executed on
+    /// this app lands in this block instead of producing a block of its own.
+    ///
+    /// Fees for signers with `FeeSetting::Auto` are estimated against the state committed by
+    /// the previous block, so such a signer can only send one tx per block.
+    /// Use `FeeSetting::Custom` to send several txs from the same signer in one block.
+    pub fn begin_block(&self) -> RunnerResult<()> {
+        self.inner.begin_block()
+    }
+
+    /// Deliver a tx to the block in progress, see `begin_block`.
+    pub fn deliver_tx<R>(&self, msgs: Vec<Any>, signer: &SigningAccount) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        self.inner.deliver_tx(msgs, signer)
+    }
+
+    /// End the block in progress and commit its state.
+    pub fn end_block_and_commit(&self) -> RunnerResult<()> {
+        self.inner.end_block_and_commit()
+    }
+
+    /// Whether a block begun with `begin_block` has not ended yet.
+    pub fn is_block_in_progress(&self) -> bool {
+        self.inner.is_block_in_progress()
+    }
+
+    /// Create an independent copy of the test environment from its current state.
+    /// Both apps can diverge freely and each one is cleaned up when dropped.
+    /// Snapshots taken from this app are not carried over to the fork.
+    pub fn fork(&self) -> RunnerResult<Self> {
+        Ok(Self {
+            inner: self.inner.fork()?,
+        })
+    }
+
+    /// Take a snapshot of the current chain state, which can later be restored
+    /// with `restore` any number of times. Snapshots live as long as the app does.
+    pub fn snapshot(&self) -> RunnerResult<SnapshotId> {
+        self.inner.snapshot()
+    }
+
+    /// Restore chain state to the given snapshot.
+    /// Everything that happened after the snapshot was taken is discarded.
+    pub fn restore(&self, snapshot: SnapshotId) -> RunnerResult<()> {
+        self.inner.restore(snapshot)
+    }
+
     /// Get the current block time as a timestamp
     pub fn get_block_timestamp(&self) -> Timestamp {
         self.inner.get_block_timestamp()
@@ -84,6 +187,52 @@ impl OsmosisTestApp {
         self.inner.init_accounts(coins, count)
     }
 
+    /// Initialize account with a private key derived from `seed`,
+    /// the same seed always gives the same address.
+    pub fn init_account_with_seed(
+        &self,
+        seed: &[u8],
+        coins: &[Coin],
+    ) -> RunnerResult<SigningAccount> {
+        self.inner.init_account_with_seed(seed, coins)
+    }
+
+    /// Initialize account with the private key of a BIP-39 `mnemonic` at `hd_path`,
+    /// e.g. `m/44'/118'/0'/0/0`.
+    pub fn init_account_from_mnemonic(
+        &self,
+        mnemonic: &str,
+        hd_path: &str,
+        coins: &[Coin],
+    ) -> RunnerResult<SigningAccount> {
+        self.inner
+            .init_account_from_mnemonic(mnemonic, hd_path, coins)
+    }
+
+    /// Initialize a multisig account of `members` with initial balance of any coins.
+    /// Members keep their order, which is part of the multisig address.
+    pub fn init_multisig_account(
+        &self,
+        members: &[&SigningAccount],
+        threshold: u32,
+        coins: &[Coin],
+    ) -> RunnerResult<MultisigAccount> {
+        self.inner.init_multisig_account(members, threshold, coins)
+    }
+
+    /// Mint `coins` to any address, contracts and module accounts included, without producing
+    /// a block. Between blocks, the coins are committed along with the next block and the app
+    /// can't be snapshotted or forked until then.
+    pub fn mint_to(&self, address: &str, coins: &[Coin]) -> RunnerResult<()> {
+        self.inner.mint_to(address, coins)
+    }
+
+    /// Mint or burn coins of any address until its balance is exactly `coins`,
+    /// without producing a block, see `mint_to`.
+    pub fn set_balance(&self, address: &str, coins: &[Coin]) -> RunnerResult<()> {
+        self.inner.set_balance(address, coins)
+    }
+
     /// Simulate transaction execution and return gas info
     pub fn simulate_tx<I>(
         &self,
@@ -132,6 +281,10 @@ impl<'a> Runner<'a> for OsmosisTestApp {
         self.inner.query(path, q)
     }
 
+    fn stored_codes(&self) -> Option<&StoredCodes> {
+        self.inner.stored_codes()
+    }
+
     fn execute_multiple_raw<R>(
         &self,
         msgs: Vec<cosmrs::Any>,
@@ -142,6 +295,57 @@ impl<'a> Runner<'a> for OsmosisTestApp {
     {
         self.inner.execute_multiple_raw(msgs, signer)
     }
+
+    fn execute_multiple_raw_multi_signer<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signers: &[&SigningAccount],
+        fee_payer: &SigningAccount,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: prost::Message + Default,
+    {
+        self.inner
+            .execute_multiple_raw_multi_signer(msgs, signers, fee_payer)
+    }
+
+    fn execute_multiple_raw_multisig<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        multisig: &MultisigAccount,
+        signers: &[&SigningAccount],
+    ) -> RunnerExecuteResult<R>
+    where
+        R: prost::Message + Default,
+    {
+        self.inner
+            .execute_multiple_raw_multisig(msgs, multisig, signers)
+    }
+
+    fn execute_as<R>(&self, sender: &str, msgs: Vec<cosmrs::Any>) -> RunnerExecuteResult<R>
+    where
+        R: prost::Message + Default,
+    {
+        self.inner.execute_as(sender, msgs)
+    }
+
+    fn execute_multiple_raw_as_contract<R>(
+        &self,
+        contract: &str,
+        msgs: Vec<cosmrs::Any>,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: prost::Message + Default,
+    {
+        self.inner.execute_multiple_raw_as_contract(contract, msgs)
+    }
+
+    fn wasm_sudo<R>(&self, contract: &str, msg: &[u8]) -> RunnerExecuteResult<R>
+    where
+        R: prost::Message + Default,
+    {
+        self.inner.wasm_sudo(contract, msg)
+    }
 }
 
 #[cfg(test)]
@@ -159,17 +363,20 @@ mod tests {
         self, MsgForceUnlock, MsgForceUnlockResponse, MsgLockTokens, MsgLockTokensResponse,
     };
     use osmosis_std::types::osmosis::tokenfactory::v1beta1::{
-        MsgCreateDenom, MsgCreateDenomResponse, QueryParamsRequest, QueryParamsResponse,
+        MsgCreateDenom, MsgCreateDenomResponse, QueryDenomsFromCreatorRequest,
+        QueryDenomsFromCreatorResponse, QueryParamsRequest, QueryParamsResponse,
     };
 
     use crate::module::Gamm;
     use crate::module::Wasm;
     use crate::runner::app::OsmosisTestApp;
     use crate::Bank;
+    use crate::Feegrant;
+    use crate::{MultisigAccount, SigningAccount};
     use test_tube::account::{Account, FeeSetting};
     use test_tube::module::Module;
     use test_tube::ExecuteResponse;
-    use test_tube::{runner::*, RunnerError};
+    use test_tube::{runner::*, sdkerrors, DecodeError, RunnerError};
 
     #[test]
     fn test_init_accounts() {
@@ -182,6 +389,542 @@ mod tests {
         assert!(accounts.get(1).is_some());
         assert!(accounts.get(2).is_some());
         assert!(accounts.get(3).is_none());
+
+        // many accounts are created in a single block
+        let height = app.get_block_height();
+        let accounts = app
+            .init_accounts(&[Coin::new(5, "uion"), Coin::new(10, "uosmo")], 1_000)
+            .unwrap();
+        assert_eq!(app.get_block_height(), height + 1);
+
+        let addresses = accounts
+            .iter()
+            .map(|acc| acc.address())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(addresses.len(), 1_000);
+
+        let balances = Bank::new(&app)
+            .query_all_balances(&QueryAllBalancesRequest {
+                address: accounts[999].address(),
+                pagination: None,
+            })
+            .unwrap()
+            .balances;
+        assert_eq!(
+            balances
+                .into_iter()
+                .map(|c| (c.denom, c.amount))
+                .collect::<Vec<_>>(),
+            vec![
+                ("uion".to_string(), "5".to_string()),
+                ("uosmo".to_string(), "10".to_string())
+            ]
+        );
+
+        assert!(app.init_accounts(&[], 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_deterministic_accounts() {
+        let mnemonic = "bottom loan skill merry east cradle onion journey palm apology verb edit desert impose absurd oil bubble sweet glove shallow size build burst effort";
+
+        let app = OsmosisTestApp::default();
+        let acc = app
+            .init_account_from_mnemonic(
+                mnemonic,
+                "m/44'/118'/0'/0/0",
+                &coins(100_000_000_000, "uosmo"),
+            )
+            .unwrap();
+        assert_eq!(acc.address(), "osmo12smx2wdlyttvyzvzg54y2vnqwq2qjateuf7thj");
+
+        let balance = Bank::new(&app)
+            .query_all_balances(&QueryAllBalancesRequest {
+                address: acc.address(),
+                pagination: None,
+            })
+            .unwrap()
+            .balances;
+        assert_eq!(balance.len(), 1);
+        assert_eq!(balance[0].amount, "100000000000");
+
+        // same seed gives the same address, and so the same contract address, on another app
+        let instantiate = |app: &OsmosisTestApp| {
+            let acc = app
+                .init_account_with_seed(b"admin", &coins(100_000_000_000, "uosmo"))
+                .unwrap();
+            let wasm = Wasm::new(app);
+            let code_id = wasm
+                .store_code_from_file("./test_artifacts/cw1_whitelist.wasm", None, &acc)
+                .unwrap()
+                .code_id;
+            let contract_addr = wasm
+                .instantiate(
+                    code_id,
+                    &cw1_whitelist::msg::InstantiateMsg {
+                        admins: vec![acc.address()],
+                        mutable: true,
+                    },
+                    None,
+                    None,
+                    &[],
+                    &acc,
+                )
+                .unwrap()
+                .data
+                .address;
+            (acc.address(), contract_addr)
+        };
+        assert_eq!(instantiate(&app), instantiate(&OsmosisTestApp::default()));
+        assert_ne!(
+            app.init_account_with_seed(b"other", &[]).unwrap().address(),
+            app.init_account_with_seed(b"admin", &[]).unwrap().address()
+        );
+
+        let res = app.init_account_from_mnemonic("not a mnemonic", "m/44'/118'/0'/0/0", &[]);
+        assert!(matches!(
+            res,
+            Err(RunnerError::DecodeError(
+                DecodeError::SigningKeyDecodeError { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_set_balance_and_mint_to() {
+        use osmosis_std::types::cosmos::bank::v1beta1::MsgSend;
+
+        let app = OsmosisTestApp::default();
+        let bank = Bank::new(&app);
+        let balances = |address: &str| {
+            bank.query_all_balances(&QueryAllBalancesRequest {
+                address: address.to_string(),
+                pagination: None,
+            })
+            .unwrap()
+            .balances
+            .into_iter()
+            .map(|c| Coin::new(c.amount.parse().unwrap(), c.denom))
+            .collect::<Vec<_>>()
+        };
+
+        let acc = app.init_account(&coins(100_000_000_000, "uosmo")).unwrap();
+        let height = app.get_block_height();
+
+        app.set_balance(
+            &acc.address(),
+            &[Coin::new(5, "uion"), Coin::new(1_000_000_000, "uosmo")],
+        )
+        .unwrap();
+        assert_eq!(
+            balances(&acc.address()),
+            vec![Coin::new(5, "uion"), Coin::new(1_000_000_000, "uosmo")]
+        );
+
+        app.mint_to(&acc.address(), &coins(10, "uion")).unwrap();
+        assert_eq!(
+            balances(&acc.address()),
+            vec![Coin::new(15, "uion"), Coin::new(1_000_000_000, "uosmo")]
+        );
+
+        // denoms left out are burned
+        app.set_balance(&acc.address(), &coins(1_000_000_000, "uosmo"))
+            .unwrap();
+        assert_eq!(balances(&acc.address()), coins(1_000_000_000, "uosmo"));
+        assert_eq!(app.get_block_height(), height);
+
+        // not committed yet
+        assert!(app.snapshot().is_err());
+
+        // funded contract
+        let wasm = Wasm::new(&app);
+        let code_id = wasm
+            .store_code_from_file("./test_artifacts/cw1_whitelist.wasm", None, &acc)
+            .unwrap()
+            .code_id;
+        let contract_addr = wasm
+            .instantiate(
+                code_id,
+                &cw1_whitelist::msg::InstantiateMsg {
+                    admins: vec![acc.address()],
+                    mutable: true,
+                },
+                None,
+                None,
+                &[],
+                &acc,
+            )
+            .unwrap()
+            .data
+            .address;
+        app.mint_to(&contract_addr, &coins(42, "uatom")).unwrap();
+        assert_eq!(balances(&contract_addr), coins(42, "uatom"));
+
+        // minted coins can be spent by txs and survive the next block
+        app.set_balance(&acc.address(), &coins(1_000_000_000_000, "uosmo"))
+            .unwrap();
+        bank.send(
+            MsgSend {
+                from_address: acc.address(),
+                to_address: contract_addr.clone(),
+                amount: vec![osmosis_std::types::cosmos::base::v1beta1::Coin {
+                    denom: "uosmo".to_string(),
+                    amount: "1".to_string(),
+                }],
+            },
+            &acc,
+        )
+        .unwrap();
+        assert_eq!(
+            balances(&contract_addr),
+            vec![Coin::new(42, "uatom"), Coin::new(1, "uosmo")]
+        );
+        app.snapshot().unwrap();
+
+        // inside a block in progress
+        app.begin_block().unwrap();
+        app.set_balance(&contract_addr, &[]).unwrap();
+        app.end_block_and_commit().unwrap();
+        assert_eq!(balances(&contract_addr), vec![]);
+    }
+
+    #[test]
+    fn test_multi_signer() {
+        use osmosis_std::types::cosmos::bank::v1beta1::{
+            Input, MsgMultiSend, MsgMultiSendResponse, Output, QueryBalanceRequest,
+        };
+        use osmosis_std::types::cosmos::base::v1beta1::Coin as ProtoCoin;
+
+        let app = OsmosisTestApp::default();
+        let initial = [
+            Coin::new(1_000, "uion"),
+            Coin::new(100_000_000_000, "uosmo"),
+        ];
+        let alice = app.init_account(&initial).unwrap();
+        let bob = app.init_account(&initial).unwrap();
+        let payer = app.init_account(&initial).unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+
+        let balance = |address: &str, denom: &str| {
+            Bank::new(&app)
+                .query_balance(&QueryBalanceRequest {
+                    address: address.to_string(),
+                    denom: denom.to_string(),
+                })
+                .unwrap()
+                .balance
+                .unwrap()
+                .amount
+                .parse::<u128>()
+                .unwrap()
+        };
+        let multi_send = |amounts: &[(&SigningAccount, u128)]| {
+            let input = |(acc, amount): &(&SigningAccount, u128)| Input {
+                address: acc.address(),
+                coins: vec![ProtoCoin {
+                    denom: "uion".to_string(),
+                    amount: amount.to_string(),
+                }],
+            };
+            let total = amounts.iter().map(|(_, amount)| amount).sum::<u128>();
+            Any {
+                type_url: MsgMultiSend::TYPE_URL.to_string(),
+                value: MsgMultiSend {
+                    inputs: amounts.iter().map(input).collect(),
+                    outputs: vec![Output {
+                        address: receiver.address(),
+                        coins: vec![ProtoCoin {
+                            denom: "uion".to_string(),
+                            amount: total.to_string(),
+                        }],
+                    }],
+                }
+                .encode_to_vec(),
+            }
+        };
+
+        // a separate account pays the fee
+        app.execute_multiple_raw_multi_signer::<MsgMultiSendResponse>(
+            vec![multi_send(&[(&alice, 100), (&bob, 200)])],
+            &[&alice, &bob],
+            &payer,
+        )
+        .unwrap();
+        assert_eq!(balance(&receiver.address(), "uion"), 300);
+        assert_eq!(balance(&alice.address(), "uion"), 900);
+        assert_eq!(balance(&bob.address(), "uion"), 800);
+        assert_eq!(balance(&alice.address(), "uosmo"), 100_000_000_000);
+        assert_eq!(balance(&bob.address(), "uosmo"), 100_000_000_000);
+        assert!(balance(&payer.address(), "uosmo") < 100_000_000_000);
+
+        // the first signer as fee payer, which leaves the payer of the fee unset
+        app.execute_multiple_raw_multi_signer::<MsgMultiSendResponse>(
+            vec![multi_send(&[(&alice, 100), (&bob, 200)])],
+            &[&alice, &bob],
+            &alice,
+        )
+        .unwrap();
+        assert_eq!(balance(&receiver.address(), "uion"), 600);
+        assert!(balance(&alice.address(), "uosmo") < 100_000_000_000);
+        assert_eq!(balance(&bob.address(), "uosmo"), 100_000_000_000);
+
+        // a signer other than the first one as fee payer
+        let alice_uosmo = balance(&alice.address(), "uosmo");
+        let payer_uosmo = balance(&payer.address(), "uosmo");
+        app.execute_multiple_raw_multi_signer::<MsgMultiSendResponse>(
+            vec![multi_send(&[(&alice, 100), (&bob, 200)])],
+            &[&alice, &bob],
+            &bob,
+        )
+        .unwrap();
+        assert_eq!(balance(&receiver.address(), "uion"), 900);
+        assert_eq!(balance(&alice.address(), "uosmo"), alice_uosmo);
+        assert_eq!(balance(&payer.address(), "uosmo"), payer_uosmo);
+        assert!(balance(&bob.address(), "uosmo") < 100_000_000_000);
+
+        // a missing signature
+        let err = app
+            .execute_multiple_raw_multi_signer::<MsgMultiSendResponse>(
+                vec![multi_send(&[(&alice, 100), (&bob, 200)])],
+                &[&alice],
+                &alice,
+            )
+            .unwrap_err();
+        assert_eq!(err.abci_code(), Some(sdkerrors::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn test_fee_grant() {
+        use osmosis_std::types::cosmos::bank::v1beta1::{MsgSend, QueryBalanceRequest};
+        use osmosis_std::types::cosmos::base::v1beta1::Coin as ProtoCoin;
+        use osmosis_std::types::cosmos::feegrant::v1beta1::{
+            BasicAllowance, MsgGrantAllowance, MsgRevokeAllowance, QueryAllowanceRequest,
+            QueryAllowancesRequest,
+        };
+
+        let app = OsmosisTestApp::default();
+        let granter = app
+            .init_account(&[Coin::new(100_000_000_000, "uosmo")])
+            .unwrap();
+        let grantee = app
+            .init_account(&[
+                Coin::new(1_000, "uion"),
+                Coin::new(100_000_000_000, "uosmo"),
+            ])
+            .unwrap();
+        let feegrant = Feegrant::new(&app);
+
+        let balance = |address: &str, denom: &str| {
+            Bank::new(&app)
+                .query_balance(&QueryBalanceRequest {
+                    address: address.to_string(),
+                    denom: denom.to_string(),
+                })
+                .unwrap()
+                .balance
+                .unwrap()
+                .amount
+                .parse::<u128>()
+                .unwrap()
+        };
+        let send = |signer: &SigningAccount| {
+            Bank::new(&app).send(
+                MsgSend {
+                    from_address: signer.address(),
+                    to_address: granter.address(),
+                    amount: vec![ProtoCoin {
+                        denom: "uion".to_string(),
+                        amount: "100".to_string(),
+                    }],
+                },
+                signer,
+            )
+        };
+
+        feegrant
+            .grant_allowance(
+                MsgGrantAllowance {
+                    granter: granter.address(),
+                    grantee: grantee.address(),
+                    allowance: Some(
+                        BasicAllowance {
+                            spend_limit: vec![],
+                            expiration: None,
+                        }
+                        .to_any(),
+                    ),
+                },
+                &granter,
+            )
+            .unwrap();
+
+        let grant = feegrant
+            .query_allowance(&QueryAllowanceRequest {
+                granter: granter.address(),
+                grantee: grantee.address(),
+            })
+            .unwrap()
+            .allowance
+            .unwrap();
+        assert_eq!(grant.granter, granter.address());
+        assert_eq!(grant.grantee, grantee.address());
+        assert_eq!(grant.allowance.unwrap().type_url, BasicAllowance::TYPE_URL);
+
+        let grants = feegrant
+            .query_allowances(&QueryAllowancesRequest {
+                grantee: grantee.address(),
+                pagination: None,
+            })
+            .unwrap()
+            .allowances;
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].granter, granter.address());
+
+        // fees of the grantee are deducted from the granter
+        let grantee = grantee.with_fee_granter(&granter.address());
+        let granter_uosmo = balance(&granter.address(), "uosmo");
+        send(&grantee).unwrap();
+        assert_eq!(balance(&grantee.address(), "uion"), 900);
+        assert_eq!(balance(&grantee.address(), "uosmo"), 100_000_000_000);
+        assert!(balance(&granter.address(), "uosmo") < granter_uosmo);
+
+        feegrant
+            .revoke_allowance(
+                MsgRevokeAllowance {
+                    granter: granter.address(),
+                    grantee: grantee.address(),
+                },
+                &granter,
+            )
+            .unwrap();
+        assert!(feegrant
+            .query_allowance(&QueryAllowanceRequest {
+                granter: granter.address(),
+                grantee: grantee.address(),
+            })
+            .is_err());
+
+        // without allowance, the granted tx fails
+        assert!(send(&grantee).is_err());
+        assert_eq!(balance(&grantee.address(), "uion"), 900);
+
+        // while the grantee can still pay its own fees
+        let grantee = grantee.without_fee_granter();
+        send(&grantee).unwrap();
+        assert!(balance(&grantee.address(), "uosmo") < 100_000_000_000);
+    }
+
+    #[test]
+    fn test_multisig() {
+        use cosmwasm_std::Empty;
+        use cw1_whitelist::msg::{AdminListResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+        use osmosis_std::types::cosmwasm::wasm::v1::{
+            MsgExecuteContract, MsgExecuteContractResponse,
+        };
+
+        let app = OsmosisTestApp::default();
+        let members = app
+            .init_accounts(&coins(100_000_000_000, "uosmo"), 4)
+            .unwrap();
+        let (a, b, c, outsider) = (&members[0], &members[1], &members[2], &members[3]);
+        let multisig = app
+            .init_multisig_account(&[a, b, c], 2, &coins(100_000_000_000, "uosmo"))
+            .unwrap();
+        assert_eq!(multisig.threshold(), 2);
+
+        // same members in the same order give the same address
+        let same = MultisigAccount::new(
+            "osmo".to_string(),
+            2,
+            vec![a.public_key(), b.public_key(), c.public_key()],
+            multisig.fee_setting().clone(),
+        );
+        assert_eq!(same.address(), multisig.address());
+
+        // usable wherever an `Account` is
+        fn account_id(account: &impl Account) -> String {
+            account.account_id().to_string()
+        }
+        assert_eq!(account_id(&multisig), multisig.address());
+        assert_eq!(multisig.prefix(), "osmo");
+        assert_eq!(multisig.multisig_public_key().public_keys.len(), 3);
+
+        // a contract administered by the multisig
+        let wasm = Wasm::new(&app);
+        let code_id = wasm
+            .store_code_from_file("./test_artifacts/cw1_whitelist.wasm", None, outsider)
+            .unwrap()
+            .code_id;
+        let contract_addr = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![multisig.address()],
+                    mutable: true,
+                },
+                None,
+                None,
+                &[],
+                outsider,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        let update_admins = |admins: Vec<String>| Any {
+            type_url: MsgExecuteContract::TYPE_URL.to_string(),
+            value: MsgExecuteContract {
+                sender: multisig.address(),
+                contract: contract_addr.clone(),
+                msg: serde_json::to_vec(&ExecuteMsg::<Empty>::UpdateAdmins { admins }).unwrap(),
+                funds: vec![],
+            }
+            .encode_to_vec(),
+        };
+
+        // below threshold
+        let err = app
+            .execute_multiple_raw_multisig::<MsgExecuteContractResponse>(
+                vec![update_admins(vec![outsider.address()])],
+                &multisig,
+                &[b],
+            )
+            .unwrap_err();
+        assert_eq!(err.abci_code(), Some(sdkerrors::UNAUTHORIZED));
+
+        // not a member
+        let err = app
+            .execute_multiple_raw_multisig::<MsgExecuteContractResponse>(
+                vec![update_admins(vec![outsider.address()])],
+                &multisig,
+                &[a, outsider],
+            )
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::GenericError(_)));
+
+        // any subset reaching the threshold, in any order
+        app.execute_multiple_raw_multisig::<MsgExecuteContractResponse>(
+            vec![update_admins(vec![multisig.address(), a.address()])],
+            &multisig,
+            &[c, a],
+        )
+        .unwrap();
+        let admin_list: AdminListResponse = wasm
+            .query(&contract_addr, &QueryMsg::<Empty>::AdminList {})
+            .unwrap();
+        assert_eq!(admin_list.admins, vec![multisig.address(), a.address()]);
+
+        // sequence is bumped, so the multisig can keep sending txs
+        app.execute_multiple_raw_multisig::<MsgExecuteContractResponse>(
+            vec![update_admins(vec![multisig.address()])],
+            &multisig,
+            &[a, b, c],
+        )
+        .unwrap();
+        let admin_list: AdminListResponse = wasm
+            .query(&contract_addr, &QueryMsg::<Empty>::AdminList {})
+            .unwrap();
+        assert_eq!(admin_list.admins, vec![multisig.address()]);
     }
 
     #[test]
@@ -191,24 +934,555 @@ mod tests {
         let block_time_nanos = app.get_block_time_nanos();
         let block_time_seconds = app.get_block_time_seconds();
 
-        app.increase_time(10u64);
+        app.increase_time(10u64);
+
+        assert_eq!(
+            app.get_block_time_nanos(),
+            block_time_nanos + 10_000_000_000
+        );
+        assert_eq!(app.get_block_time_seconds(), block_time_seconds + 10);
+    }
+
+    #[test]
+    fn test_get_block_height() {
+        let app = OsmosisTestApp::default();
+
+        assert_eq!(app.get_block_height(), 1i64);
+
+        app.increase_time(10u64);
+
+        assert_eq!(app.get_block_height(), 2i64);
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let app = OsmosisTestApp::default();
+        let acc = app
+            .init_account(&coins(100_000_000_000_000, "uosmo"))
+            .unwrap();
+
+        let wasm = Wasm::new(&app);
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, &acc)
+            .unwrap()
+            .data
+            .code_id;
+
+        let snapshot = app.snapshot().unwrap();
+        let height = app.get_block_height();
+        let time = app.get_block_time_nanos();
+
+        let denoms_from_creator = || {
+            app.query::<QueryDenomsFromCreatorRequest, QueryDenomsFromCreatorResponse>(
+                "/osmosis.tokenfactory.v1beta1.Query/DenomsFromCreator",
+                &QueryDenomsFromCreatorRequest {
+                    creator: acc.address(),
+                },
+            )
+            .unwrap()
+            .denoms
+        };
+
+        // restoring twice must give the same state both times
+        for _ in 0..2 {
+            app.execute::<_, MsgCreateDenomResponse>(
+                MsgCreateDenom {
+                    sender: acc.address(),
+                    subdenom: "newdenom".to_string(),
+                },
+                MsgCreateDenom::TYPE_URL,
+                &acc,
+            )
+            .unwrap();
+            assert_eq!(denoms_from_creator().len(), 1);
+
+            app.restore(snapshot).unwrap();
+
+            assert_eq!(denoms_from_creator(), Vec::<String>::new());
+            assert_eq!(app.get_block_height(), height);
+            assert_eq!(app.get_block_time_nanos(), time);
+
+            // stored code survives the restore
+            let new_code_id = wasm
+                .store_code(&wasm_byte_code, None, &acc)
+                .unwrap()
+                .data
+                .code_id;
+            assert_eq!(new_code_id, code_id + 1);
+
+            app.restore(snapshot).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_fork() {
+        use rayon::prelude::*;
+
+        let app = OsmosisTestApp::default();
+        let creator = app
+            .init_account(&coins(100_000_000_000_000, "uosmo"))
+            .unwrap();
+
+        let create_denom = |app: &OsmosisTestApp, creator: &SigningAccount, subdenom: &str| {
+            app.execute::<_, MsgCreateDenomResponse>(
+                MsgCreateDenom {
+                    sender: creator.address(),
+                    subdenom: subdenom.to_string(),
+                },
+                MsgCreateDenom::TYPE_URL,
+                creator,
+            )
+            .unwrap()
+            .data
+            .new_token_denom
+        };
+        let denoms_from_creator = |app: &OsmosisTestApp, creator: String| {
+            app.query::<QueryDenomsFromCreatorRequest, QueryDenomsFromCreatorResponse>(
+                "/osmosis.tokenfactory.v1beta1.Query/DenomsFromCreator",
+                &QueryDenomsFromCreatorRequest { creator },
+            )
+            .unwrap()
+            .denoms
+        };
+
+        let shared_denom = create_denom(&app, &creator, "shared");
+
+        let fork = app.fork().unwrap();
+        assert_eq!(fork.get_block_height(), app.get_block_height());
+        assert_eq!(fork.get_block_time_nanos(), app.get_block_time_nanos());
+
+        // forks diverge from each other
+        let forked_denom = create_denom(&fork, &creator, "forked");
+        assert_eq!(
+            denoms_from_creator(&app, creator.address()),
+            vec![shared_denom.clone()]
+        );
+        assert_eq!(
+            denoms_from_creator(&fork, creator.address()),
+            vec![shared_denom.clone(), forked_denom]
+        );
+
+        // cleaning up the fork must not affect the original app
+        drop(fork);
+        create_denom(&app, &creator, "original");
+        assert_eq!(denoms_from_creator(&app, creator.address()).len(), 2);
+
+        // scenarios from the same setup can run in parallel,
+        // signing accounts are not `Sync` so each thread creates its own
+        let denom_counts = (0..4)
+            .into_par_iter()
+            .map(|i| {
+                let fork = app.fork().unwrap();
+                let acc = fork
+                    .init_account(&coins(100_000_000_000_000, "uosmo"))
+                    .unwrap();
+                for j in 0..i {
+                    create_denom(&fork, &acc, &format!("parallel{}", j));
+                }
+                denoms_from_creator(&fork, acc.address()).len()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(denom_counts, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_export_and_import_genesis() {
+        use cw1_whitelist::msg::InstantiateMsg;
+
+        let app = OsmosisTestApp::default();
+        let acc = app
+            .init_account(&coins(100_000_000_000_000, "uosmo"))
+            .unwrap();
+
+        let wasm = Wasm::new(&app);
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, &acc)
+            .unwrap()
+            .data
+            .code_id;
+
+        let denom = app
+            .execute::<_, MsgCreateDenomResponse>(
+                MsgCreateDenom {
+                    sender: acc.address(),
+                    subdenom: "genesis".to_string(),
+                },
+                MsgCreateDenom::TYPE_URL,
+                &acc,
+            )
+            .unwrap()
+            .data
+            .new_token_denom;
+
+        let path = std::env::temp_dir().join(format!(
+            "osmosis-test-tube-genesis-{}.json",
+            std::process::id()
+        ));
+        app.export_genesis(&path).unwrap();
+
+        let imported = OsmosisTestApp::from_genesis(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // chain continues from the next height
+        assert_eq!(imported.get_block_height(), app.get_block_height() + 1);
+
+        // state is carried over
+        let denoms = imported
+            .query::<QueryDenomsFromCreatorRequest, QueryDenomsFromCreatorResponse>(
+                "/osmosis.tokenfactory.v1beta1.Query/DenomsFromCreator",
+                &QueryDenomsFromCreatorRequest {
+                    creator: acc.address(),
+                },
+            )
+            .unwrap()
+            .denoms;
+        assert_eq!(denoms, vec![denom]);
+
+        let bank = Bank::new(&app);
+        let imported_bank = Bank::new(&imported);
+        let balances_req = QueryAllBalancesRequest {
+            address: acc.address(),
+            pagination: None,
+        };
+        assert_eq!(
+            imported_bank.query_all_balances(&balances_req).unwrap(),
+            bank.query_all_balances(&balances_req).unwrap()
+        );
+
+        // existing accounts and code can be used on the imported chain
+        let imported_wasm = Wasm::new(&imported);
+        imported_wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![],
+                    mutable: true,
+                },
+                None,
+                None,
+                &[],
+                &acc,
+            )
+            .unwrap();
+
+        // validator private keys are not part of genesis
+        assert!(matches!(
+            imported.get_first_validator_signing_account(),
+            Err(RunnerError::QueryError { .. })
+        ));
+
+        // unknown file
+        let err = OsmosisTestApp::from_genesis(path).unwrap_err();
+        assert!(matches!(err, RunnerError::IoError(_)));
+    }
+
+    #[test]
+    fn test_custom_chain_id_and_fee_denom() {
+        use test_tube::BaseApp;
+
+        let app = OsmosisTestApp::from_base_app(BaseApp::new("ufoo", "foo-1", "osmo", 1.2));
+
+        // fees are paid in the custom denom
+        let acc = app.init_account(&coins(100_000_000_000, "ufoo")).unwrap();
+        app.execute::<_, MsgCreateDenomResponse>(
+            MsgCreateDenom {
+                sender: acc.address(),
+                subdenom: "foo".to_string(),
+            },
+            MsgCreateDenom::TYPE_URL,
+            &acc,
+        )
+        .unwrap();
+
+        // validators are funded with it as well
+        let validator = app.get_first_validator_signing_account().unwrap();
+        let balances = Bank::new(&app)
+            .query_all_balances(&QueryAllBalancesRequest {
+                address: validator.address(),
+                pagination: None,
+            })
+            .unwrap()
+            .balances;
+        assert!(balances.iter().any(|c| c.denom == "ufoo"));
+        assert!(balances.iter().all(|c| c.denom != "uosmo"));
+    }
+
+    #[test]
+    fn test_bech32_prefix_is_process_wide() {
+        use test_tube::{BaseApp, TestEnvConfig};
+
+        let app = OsmosisTestApp::default();
+
+        // another prefix would change address encoding of `app` as well
+        let err = BaseApp::new_with_config(&TestEnvConfig::new("foo-1", "uosmo", "foo"), 1.2)
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+
+        // while the same one is fine
+        assert!(
+            BaseApp::new_with_config(&TestEnvConfig::new("foo-1", "uosmo", "osmo"), 1.2).is_ok()
+        );
+        assert!(app
+            .init_account(&[])
+            .unwrap()
+            .address()
+            .starts_with("osmo1"));
+    }
+
+    #[test]
+    fn test_manual_block_production() {
+        let app = OsmosisTestApp::default();
+        let accs = app
+            .init_accounts(&coins(100_000_000_000_000, "uosmo"), 2)
+            .unwrap();
+
+        let create_denom_msg = |acc: &SigningAccount, subdenom: &str| {
+            MsgCreateDenom {
+                sender: acc.address(),
+                subdenom: subdenom.to_string(),
+            }
+            .to_any()
+        };
+
+        let height = app.get_block_height();
+        let time = app.get_block_time_nanos();
+
+        assert!(!app.is_block_in_progress());
+        app.begin_block().unwrap();
+        assert!(app.is_block_in_progress());
+        assert_eq!(
+            app.begin_block().unwrap_err(),
+            RunnerError::GenericError("block is already in progress".to_string())
+        );
+
+        // txs from different signers land in the same block
+        app.deliver_tx::<MsgCreateDenomResponse>(
+            vec![create_denom_msg(&accs[0], "first").into()],
+            &accs[0],
+        )
+        .unwrap();
+        app.execute::<_, MsgCreateDenomResponse>(
+            MsgCreateDenom {
+                sender: accs[1].address(),
+                subdenom: "second".to_string(),
+            },
+            MsgCreateDenom::TYPE_URL,
+            &accs[1],
+        )
+        .unwrap();
+
+        // accounts created mid-block land in it too
+        let acc = app
+            .init_account(&coins(100_000_000_000_000, "uosmo"))
+            .unwrap()
+            .with_fee_setting(FeeSetting::Custom {
+                amount: Coin::new(25_000_000_000, "uosmo"),
+                gas_limit: 10_000_000,
+            });
+
+        // same signer can send several txs with custom fee
+        for subdenom in ["third", "fourth"] {
+            app.deliver_tx::<MsgCreateDenomResponse>(
+                vec![create_denom_msg(&acc, subdenom).into()],
+                &acc,
+            )
+            .unwrap();
+        }
+
+        // committed state can't be snapshotted or forked mid-block
+        assert!(app.snapshot().is_err());
+        assert!(app.fork().is_err());
+
+        app.end_block_and_commit().unwrap();
+        assert!(!app.is_block_in_progress());
+        assert_eq!(app.get_block_height(), height + 1);
+        assert_eq!(app.get_block_time_nanos(), time + 5_000_000_000);
+
+        let denoms = app
+            .query::<QueryDenomsFromCreatorRequest, QueryDenomsFromCreatorResponse>(
+                "/osmosis.tokenfactory.v1beta1.Query/DenomsFromCreator",
+                &QueryDenomsFromCreatorRequest {
+                    creator: acc.address(),
+                },
+            )
+            .unwrap()
+            .denoms;
+        assert_eq!(denoms.len(), 2);
+
+        // back to one block per tx
+        app.deliver_tx::<MsgCreateDenomResponse>(
+            vec![create_denom_msg(&accs[0], "fifth").into()],
+            &accs[0],
+        )
+        .unwrap_err();
+        assert_eq!(
+            app.end_block_and_commit().unwrap_err(),
+            RunnerError::GenericError("no block in progress, call `begin_block` first".to_string())
+        );
+        app.execute::<_, MsgCreateDenomResponse>(
+            MsgCreateDenom {
+                sender: accs[0].address(),
+                subdenom: "fifth".to_string(),
+            },
+            MsgCreateDenom::TYPE_URL,
+            &accs[0],
+        )
+        .unwrap();
+        assert_eq!(app.get_block_height(), height + 2);
+    }
+
+    #[test]
+    fn test_block_time() {
+        use std::time::Duration;
+
+        let app = OsmosisTestApp::default();
+        let acc = app
+            .init_account(&coins(100_000_000_000_000, "uosmo"))
+            .unwrap();
+
+        let create_denom = |subdenom: &str| {
+            app.execute::<_, MsgCreateDenomResponse>(
+                MsgCreateDenom {
+                    sender: acc.address(),
+                    subdenom: subdenom.to_string(),
+                },
+                MsgCreateDenom::TYPE_URL,
+                &acc,
+            )
+            .unwrap();
+        };
+
+        // default interval
+        let time = app.get_block_timestamp();
+        create_denom("default");
+        assert_eq!(app.get_block_timestamp(), time.plus_seconds(5));
+
+        // custom interval
+        app.set_block_interval(Duration::from_millis(1_500));
+        let time = app.get_block_timestamp();
+        create_denom("interval");
+        assert_eq!(app.get_block_timestamp(), time.plus_nanos(1_500_000_000));
+
+        // next block lands exactly at the given time, then interval applies again
+        let target = time.plus_seconds(30 * 24 * 60 * 60);
+        app.set_block_time(target).unwrap();
+        create_denom("absolute");
+        assert_eq!(app.get_block_timestamp(), target);
+        create_denom("after");
+        assert_eq!(app.get_block_timestamp(), target.plus_nanos(1_500_000_000));
+
+        // can't go back in time
+        let err = app.set_block_time(target).unwrap_err();
+        assert!(matches!(err, RunnerError::ExecuteError { .. }));
+
+        // increase time discards the next block time
+        app.set_block_time(target.plus_seconds(24 * 60 * 60))
+            .unwrap();
+        app.increase_time(10);
+        let time = app.get_block_timestamp();
+        assert_eq!(time, target.plus_nanos(1_500_000_000).plus_seconds(10));
+        create_denom("increased");
+        assert_eq!(app.get_block_timestamp(), time.plus_nanos(1_500_000_000));
+    }
+
+    #[test]
+    fn test_skip_blocks_and_advance_to_next_epoch() {
+        use osmosis_std::types::osmosis::epochs::v1beta1::{
+            QueryCurrentEpochRequest, QueryCurrentEpochResponse,
+        };
+
+        let app = OsmosisTestApp::default();
+
+        let height = app.get_block_height();
+        let time = app.get_block_timestamp();
+        app.skip_blocks(10).unwrap();
+        assert_eq!(app.get_block_height(), height + 10);
+        assert_eq!(app.get_block_timestamp(), time.plus_seconds(50));
+
+        let current_epoch = |identifier: &str| {
+            app.query::<QueryCurrentEpochRequest, QueryCurrentEpochResponse>(
+                "/osmosis.epochs.v1beta1.Query/CurrentEpoch",
+                &QueryCurrentEpochRequest {
+                    identifier: identifier.to_string(),
+                },
+            )
+            .unwrap()
+            .current_epoch
+        };
+
+        for identifier in ["day", "week"] {
+            let epoch = current_epoch(identifier);
+            let height = app.get_block_height();
+
+            app.advance_to_next_epoch(identifier).unwrap();
+
+            assert_eq!(current_epoch(identifier), epoch + 1);
+            assert_eq!(app.get_block_height(), height + 1);
+        }
 
+        let err = app.advance_to_next_epoch("unknown").unwrap_err();
         assert_eq!(
-            app.get_block_time_nanos(),
-            block_time_nanos + 10_000_000_000
+            err,
+            RunnerError::ExecuteError {
+                msg: "epoch `unknown` not found".to_string()
+            }
         );
-        assert_eq!(app.get_block_time_seconds(), block_time_seconds + 10);
     }
 
     #[test]
-    fn test_get_block_height() {
+    fn test_tx_error() {
+        use osmosis_std::types::cosmos::bank::v1beta1::{MsgSend, MsgSendResponse};
+
         let app = OsmosisTestApp::default();
+        let sender = app
+            .init_account(&coins(100_000_000_000_000, "uosmo"))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
 
-        assert_eq!(app.get_block_height(), 1i64);
+        let (from_address, to_address) = (sender.address(), receiver.address());
+        let send_msg = |amount: u128| MsgSend {
+            from_address: from_address.clone(),
+            to_address: to_address.clone(),
+            amount: vec![Coin::new(amount, "uosmo").into()],
+        };
 
-        app.increase_time(10u64);
+        // fails when simulating for fee estimation
+        let err = app
+            .execute::<_, MsgSendResponse>(
+                send_msg(1_000_000_000_000_000),
+                MsgSend::TYPE_URL,
+                &sender,
+            )
+            .unwrap_err();
+        assert_eq!(err.abci_code(), Some(sdkerrors::INSUFFICIENT_FUNDS));
 
-        assert_eq!(app.get_block_height(), 2i64);
+        // fails when delivering the tx
+        let gas_limit = 10_000_000;
+        let sender = sender.with_fee_setting(FeeSetting::Custom {
+            amount: Coin::new(25_000_000_000, "uosmo"),
+            gas_limit,
+        });
+        let err = app
+            .execute_multiple_raw::<MsgSendResponse>(
+                vec![
+                    send_msg(1).to_any().into(),
+                    send_msg(1_000_000_000_000_000).to_any().into(),
+                ],
+                &sender,
+            )
+            .unwrap_err();
+        match err {
+            RunnerError::TxError(err) => {
+                assert_eq!(err.code(), sdkerrors::INSUFFICIENT_FUNDS);
+                assert_eq!(err.msg_index, Some(1));
+                assert_eq!(err.gas_wanted, gas_limit);
+                assert!(err.gas_used > 0);
+            }
+            err => panic!("unexpected error: {:?}", err),
+        }
     }
 
     #[test]
@@ -403,6 +1677,487 @@ mod tests {
 
         assert_eq!(admin_list.admins, new_admins);
         assert!(admin_list.mutable);
+
+        // former admin is no longer authorized
+        let err = wasm
+            .execute::<ExecuteMsg>(
+                &contract_addr,
+                &ExecuteMsg::UpdateAdmins {
+                    admins: init_admins,
+                },
+                &[],
+                admin,
+            )
+            .unwrap_err();
+        let contract_err = err.contract_error().unwrap();
+        assert!(contract_err.matches(&cw1_whitelist::ContractError::Unauthorized {}));
+        assert_eq!(contract_err.msg_index, Some(0));
+    }
+
+    #[test]
+    fn test_wasm_admin_and_contract_queries() {
+        use cw1_whitelist::msg::*;
+
+        let app = OsmosisTestApp::default();
+        let accs = app
+            .init_accounts(&coins(1_000_000_000_000, "uosmo"), 2)
+            .unwrap();
+        let admin = &accs[0];
+        let new_admin = &accs[1];
+
+        let wasm = Wasm::new(&app);
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, admin)
+            .unwrap()
+            .data
+            .code_id;
+
+        let init_msg = InstantiateMsg {
+            admins: vec![admin.address()],
+            mutable: true,
+        };
+        let contract_addr = wasm
+            .instantiate(
+                code_id,
+                &init_msg,
+                Some(&admin.address()),
+                Some("cw1"),
+                &[],
+                admin,
+            )
+            .unwrap()
+            .data
+            .address;
+        let contract2_addr = wasm
+            .instantiate2(code_id, &init_msg, None, Some("cw1"), &[], b"salt", admin)
+            .unwrap()
+            .data
+            .address;
+
+        let code_info = wasm.code_info(code_id).unwrap();
+        assert_eq!(code_info.creator, admin.address());
+
+        let mut contracts = wasm.contracts_by_code(code_id, None).unwrap().contracts;
+        contracts.sort();
+        let mut expected = vec![contract_addr.clone(), contract2_addr];
+        expected.sort();
+        assert_eq!(contracts, expected);
+
+        // raw storage of `ADMIN_LIST`
+        let admin_list: AdminListResponse =
+            serde_json::from_slice(&wasm.raw_query(&contract_addr, b"admin_list").unwrap())
+                .unwrap();
+        assert_eq!(admin_list.admins, vec![admin.address()]);
+        assert!(wasm
+            .raw_query(&contract_addr, b"unknown")
+            .unwrap()
+            .is_empty());
+
+        let contract_info = wasm
+            .contract_info(&contract_addr)
+            .unwrap()
+            .contract_info
+            .unwrap();
+        assert_eq!(contract_info.code_id, code_id);
+        assert_eq!(contract_info.admin, admin.address());
+        assert_eq!(contract_info.label, "cw1");
+
+        wasm.update_admin(&contract_addr, &new_admin.address(), admin)
+            .unwrap();
+        let contract_info = wasm.contract_info(&contract_addr).unwrap().contract_info;
+        assert_eq!(contract_info.unwrap().admin, new_admin.address());
+
+        wasm.clear_admin(&contract_addr, new_admin).unwrap();
+        let contract_info = wasm.contract_info(&contract_addr).unwrap().contract_info;
+        assert_eq!(contract_info.unwrap().admin, "");
+
+        // no one can migrate without admin
+        let err = wasm
+            .migrate(&contract_addr, code_id, &cosmwasm_std::Empty {}, new_admin)
+            .unwrap_err();
+        assert_eq!(err.abci_code(), Some(sdkerrors::UNAUTHORIZED));
+
+        let history = wasm.contract_history(&contract_addr, None).unwrap().entries;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].code_id, code_id);
+    }
+
+    #[test]
+    fn test_execute_as() {
+        use cw1_whitelist::msg::InstantiateMsg;
+        use osmosis_std::types::cosmwasm::wasm::v1::{MsgUpdateAdmin, MsgUpdateAdminResponse};
+
+        // gov module account, which has no key to sign with
+        let gov = "osmo10d07y265gmmuvt4z0w9aw880jnsr700jjeq4qp";
+
+        let app = OsmosisTestApp::default();
+        let acc = app
+            .init_account(&coins(1_000_000_000_000, "uosmo"))
+            .unwrap();
+
+        let wasm = Wasm::new(&app);
+        let code_id = wasm
+            .store_code_from_file("./test_artifacts/cw1_whitelist.wasm", None, &acc)
+            .unwrap()
+            .code_id;
+        let contract_addr = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![],
+                    mutable: false,
+                },
+                Some(gov),
+                None,
+                &[],
+                &acc,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        let update_admin = |sender: &str| -> Any {
+            MsgUpdateAdmin {
+                sender: sender.to_string(),
+                new_admin: acc.address(),
+                contract: contract_addr.clone(),
+            }
+            .to_any()
+            .into()
+        };
+
+        // messages must be signed by the impersonated address
+        let err = app
+            .execute_as::<MsgUpdateAdminResponse>(gov, vec![update_admin(&acc.address())])
+            .unwrap_err();
+        assert_eq!(err.abci_code(), Some(sdkerrors::UNAUTHORIZED));
+
+        app.execute_as::<MsgUpdateAdminResponse>(gov, vec![update_admin(gov)])
+            .unwrap();
+        let contract_info = wasm.contract_info(&contract_addr).unwrap().contract_info;
+        assert_eq!(contract_info.unwrap().admin, acc.address());
+    }
+
+    #[test]
+    fn test_typed_contract() {
+        use crate::Contract;
+        use cosmwasm_std::Empty;
+        use cw1_whitelist::msg::*;
+
+        let app = OsmosisTestApp::default();
+        let accs = app
+            .init_accounts(&coins(1_000_000_000_000, "uosmo"), 2)
+            .unwrap();
+        let admin = &accs[0];
+        let other = &accs[1];
+
+        let wasm = Wasm::new(&app);
+        let code_id = wasm
+            .store_code_from_file("./test_artifacts/cw1_whitelist.wasm", None, admin)
+            .unwrap()
+            .code_id;
+
+        let contract: Contract<_, InstantiateMsg, ExecuteMsg<Empty>, QueryMsg<Empty>> = wasm
+            .instantiate_contract(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![admin.address()],
+                    mutable: true,
+                },
+                None,
+                None,
+                &[],
+                admin,
+            )
+            .unwrap();
+        assert_eq!(contract.code_id(), code_id);
+
+        contract
+            .execute(
+                &ExecuteMsg::UpdateAdmins {
+                    admins: vec![other.address()],
+                },
+                &[],
+                admin,
+            )
+            .unwrap();
+        let admin_list: AdminListResponse = contract.query(&QueryMsg::AdminList {}).unwrap();
+        assert_eq!(admin_list.admins, vec![other.address()]);
+
+        // handle to an existing contract
+        let same: Contract<_, InstantiateMsg, ExecuteMsg<Empty>, QueryMsg<Empty>> =
+            Contract::new(&app, contract.address(), code_id);
+        let admin_list: AdminListResponse = same.query(&QueryMsg::AdminList {}).unwrap();
+        assert_eq!(admin_list.admins, vec![other.address()]);
+    }
+
+    #[test]
+    fn test_store_code_from_file() {
+        use osmosis_std::types::cosmwasm::wasm::v1::{AccessConfig, AccessType};
+
+        let app = OsmosisTestApp::default();
+        let acc = app
+            .init_account(&coins(1_000_000_000_000, "uosmo"))
+            .unwrap();
+        let wasm = Wasm::new(&app);
+        let path = "./test_artifacts/cw1_whitelist.wasm";
+
+        let snapshot = app.snapshot().unwrap();
+
+        let res = wasm.store_code_from_file(path, None, &acc).unwrap();
+        assert_eq!(res.code_id, 1);
+
+        // same code is not stored again
+        let res = wasm.store_code_from_file(path, None, &acc).unwrap();
+        assert_eq!(res.code_id, 1);
+        assert_eq!(wasm.code_info(1).unwrap().data_hash, res.checksum);
+
+        // unless instantiate permission differs
+        let nobody = AccessConfig {
+            permission: AccessType::Nobody.into(),
+            address: String::new(),
+            addresses: vec![],
+        };
+        let res = wasm
+            .store_code_from_file(path, Some(nobody.clone()), &acc)
+            .unwrap();
+        assert_eq!(res.code_id, 2);
+
+        // code stored after the snapshot is gone once restored
+        app.restore(snapshot).unwrap();
+        assert!(wasm.code_info(1).is_err());
+        let res = wasm.store_code_from_file(path, None, &acc).unwrap();
+        assert_eq!(res.code_id, 1);
+
+        // code id 1 holding the same code with another permission is not mistaken for it
+        app.restore(snapshot).unwrap();
+        let res = wasm
+            .store_code_from_file(path, Some(nobody.clone()), &acc)
+            .unwrap();
+        assert_eq!(res.code_id, 1);
+        let res = wasm.store_code_from_file(path, None, &acc).unwrap();
+        assert_eq!(res.code_id, 2);
+        assert_ne!(
+            wasm.code_info(2).unwrap().instantiate_permission,
+            Some(nobody.clone())
+        );
+        let res = wasm
+            .store_code_from_file(path, Some(nobody.clone()), &acc)
+            .unwrap();
+        assert_eq!(res.code_id, 1);
+
+        // forks hold the same codes
+        let forked = app.fork().unwrap();
+        let res = Wasm::new(&forked)
+            .store_code_from_file(path, Some(nobody), &acc)
+            .unwrap();
+        assert_eq!(res.code_id, 1);
+        assert!(Wasm::new(&forked).code_info(3).is_err());
+
+        // while other apps keep track of their own codes
+        let other = OsmosisTestApp::default();
+        let other_acc = other
+            .init_account(&coins(1_000_000_000_000, "uosmo"))
+            .unwrap();
+        let other_wasm = Wasm::new(&other);
+        let res = other_wasm
+            .store_code_from_file(path, None, &other_acc)
+            .unwrap();
+        assert_eq!(res.code_id, 1);
+        assert_eq!(
+            other_wasm.code_info(1).unwrap().creator,
+            other_acc.address()
+        );
+
+        let err = wasm
+            .store_code_from_file("./test_artifacts/unknown.wasm", None, &acc)
+            .unwrap_err();
+        assert!(matches!(err, RunnerError::IoError(_)));
+    }
+
+    #[test]
+    fn test_wasm_sudo() {
+        use osmosis_std::types::osmosis::tokenfactory::v1beta1::Params;
+        use serde_json::json;
+
+        let app = OsmosisTestApp::default();
+        let acc = app
+            .init_account(&coins(1_000_000_000_000, "uosmo"))
+            .unwrap();
+
+        // transmuter creates its share denom on instantiation
+        app.set_param_set(
+            "tokenfactory",
+            osmosis_std::shim::Any {
+                type_url: Params::TYPE_URL.to_string(),
+                value: Params {
+                    denom_creation_fee: vec![],
+                    denom_creation_gas_consume: 0,
+                }
+                .encode_to_vec(),
+            },
+        )
+        .unwrap();
+
+        let wasm = Wasm::new(&app);
+        let wasm_byte_code = std::fs::read("./test_artifacts/transmuter.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, &acc)
+            .unwrap()
+            .data
+            .code_id;
+        let contract_addr = wasm
+            .instantiate(
+                code_id,
+                &json!({ "pool_asset_denoms": ["uosmo", "uion"] }),
+                None,
+                None,
+                &[],
+                &acc,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        let is_active = |wasm: &Wasm<OsmosisTestApp>| {
+            wasm.query::<_, serde_json::Value>(&contract_addr, &json!({ "is_active": {} }))
+                .unwrap()["is_active"]
+                .clone()
+        };
+        assert_eq!(is_active(&wasm), json!(true));
+
+        let res = wasm
+            .sudo(
+                &contract_addr,
+                &json!({ "set_active": { "is_active": false } }),
+            )
+            .unwrap();
+        res.assert_has_event("sudo", &[("_contract_address", &contract_addr)]);
+        assert_eq!(is_active(&wasm), json!(false));
+
+        // contracts without sudo entry point
+        let cw1_code_id = wasm
+            .store_code(
+                &std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap(),
+                None,
+                &acc,
+            )
+            .unwrap()
+            .data
+            .code_id;
+        let cw1_addr = wasm
+            .instantiate(
+                cw1_code_id,
+                &cw1_whitelist::msg::InstantiateMsg {
+                    admins: vec![],
+                    mutable: false,
+                },
+                None,
+                None,
+                &[],
+                &acc,
+            )
+            .unwrap()
+            .data
+            .address;
+        let err = wasm.sudo(&cw1_addr, &json!({})).unwrap_err();
+        assert!(err
+            .contract_error()
+            .unwrap()
+            .msg
+            .contains("Missing export sudo"));
+    }
+
+    #[test]
+    fn test_execute_response_as_contract() {
+        use cosmwasm_std::{BankMsg, CosmosMsg, Response};
+        use cw1_whitelist::msg::InstantiateMsg;
+        use osmosis_std::types::cosmos::bank::v1beta1::QueryBalanceRequest;
+
+        let app = OsmosisTestApp::default();
+        let admin = app
+            .init_account(&coins(1_000_000_000_000, "uosmo"))
+            .unwrap();
+        let receiver = app.init_account(&[]).unwrap();
+
+        let wasm = Wasm::new(&app);
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, &admin)
+            .unwrap()
+            .data
+            .code_id;
+        let contract_addr = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![admin.address()],
+                    mutable: true,
+                },
+                None,
+                None,
+                &coins(100_000_000_000, "uosmo"),
+                &admin,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        let balance_of = |address: String| {
+            Bank::new(&app)
+                .query_balance(&QueryBalanceRequest {
+                    address,
+                    denom: "uosmo".to_string(),
+                })
+                .unwrap()
+                .balance
+                .unwrap()
+                .amount
+        };
+
+        let response = Response::new()
+            .add_message(BankMsg::Send {
+                to_address: receiver.address(),
+                amount: coins(100, "uosmo"),
+            })
+            .add_message(CosmosMsg::from(MsgCreateDenom {
+                sender: contract_addr.clone(),
+                subdenom: "denom".to_string(),
+            }));
+        let res = wasm
+            .execute_response_as_contract(&contract_addr, &response)
+            .unwrap();
+
+        assert_eq!(balance_of(receiver.address()), "100");
+        assert_eq!(
+            res.msg_response::<MsgCreateDenomResponse>(1)
+                .unwrap()
+                .new_token_denom,
+            format!("factory/{}/denom", contract_addr)
+        );
+
+        // messages are executed atomically
+        let response = Response::new()
+            .add_message(BankMsg::Send {
+                to_address: receiver.address(),
+                amount: coins(100, "uosmo"),
+            })
+            .add_message(BankMsg::Send {
+                to_address: receiver.address(),
+                amount: coins(1_000_000_000_000, "uosmo"),
+            });
+        let err = wasm
+            .execute_response_as_contract(&contract_addr, &response)
+            .unwrap_err();
+        assert_eq!(err.abci_code(), Some(sdkerrors::INSUFFICIENT_FUNDS));
+        assert_eq!(balance_of(receiver.address()), "100");
+
+        // only contracts can be used as sender
+        let err = wasm
+            .execute_response_as_contract(&admin.address(), &Response::new())
+            .unwrap_err();
+        assert_eq!(err.abci_code(), Some(sdkerrors::INVALID_ADDRESS));
     }
 
     #[test]
@@ -537,9 +2292,14 @@ mod tests {
             .unwrap_err();
 
         // should fail
-        assert_eq!(err,  RunnerError::ExecuteError {
-            msg: format!("failed to execute message; message index: 0: Sender ({}) not allowed to force unlock: unauthorized", whitelisted_user.address()),
-        });
+        assert_eq!(err.abci_code(), Some(sdkerrors::UNAUTHORIZED));
+        match err {
+            RunnerError::TxError(err) => {
+                assert_eq!(err.log, format!("failed to execute message; message index: 0: Sender ({}) not allowed to force unlock: unauthorized", whitelisted_user.address()));
+                assert_eq!(err.msg_index, Some(0));
+            }
+            err => panic!("unexpected error: {:?}", err),
+        }
 
         // add whitelisted user to param set
         app.set_param_set(
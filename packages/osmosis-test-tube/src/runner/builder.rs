@@ -0,0 +1,260 @@
+use cosmrs::Any;
+use cosmwasm_std::{Timestamp, Uint128};
+use test_tube::runner::result::RunnerResult;
+use test_tube::{BaseApp, TestEnvConfig};
+
+use crate::runner::app::{
+    OsmosisTestApp, CHAIN_ID, DEFAULT_GAS_ADJUSTMENT, FEE_DENOM, OSMO_ADDRESS_PREFIX,
+};
+
+/// Builder for an [`OsmosisTestApp`] with customized genesis.
+///
+/// ```rust
+/// use osmosis_test_tube::OsmosisTestAppBuilder;
+/// use serde_json::json;
+///
+/// // only governance can upload code
+/// let app = OsmosisTestAppBuilder::new()
+///     .with_chain_id("osmo-test-1")
+///     .with_genesis(
+///         "wasm",
+///         json!({ "params": { "code_upload_access": { "permission": "Nobody" } } }),
+///     )
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OsmosisTestAppBuilder {
+    config: TestEnvConfig,
+}
+
+impl Default for OsmosisTestAppBuilder {
+    fn default() -> Self {
+        OsmosisTestAppBuilder::new()
+    }
+}
+
+impl OsmosisTestAppBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: TestEnvConfig::new(CHAIN_ID, FEE_DENOM, OSMO_ADDRESS_PREFIX),
+        }
+    }
+
+    /// Set the chain id, defaults to `osmosis-1`.
+    pub fn with_chain_id(mut self, chain_id: &str) -> Self {
+        self.config = self.config.with_chain_id(chain_id);
+        self
+    }
+
+    /// Set the genesis time, defaults to the current time.
+    pub fn with_genesis_time(mut self, genesis_time: Timestamp) -> Self {
+        self.config = self.config.with_genesis_time(genesis_time);
+        self
+    }
+
+    /// Override the genesis state of a module, e.g. `"wasm"` or `"epochs"`.
+    /// `state` is deep merged into the default genesis state: objects are merged key by key
+    /// while any other value, arrays included, replaces the default one.
+    pub fn with_genesis(mut self, module: &str, state: serde_json::Value) -> Self {
+        self.config = self.config.with_genesis(module, state);
+        self
+    }
+
+    /// Set parameter set for a given subspace before the first block.
+    pub fn with_param_set(mut self, subspace: &str, pset: impl Into<Any>) -> Self {
+        self.config = self.config.with_param_set(subspace, pset);
+        self
+    }
+
+    /// Add a bonded validator with the given self bond in `uosmo` to the initial validator set.
+    /// A single validator is created if none is added.
+    pub fn with_validator(mut self, self_bond: impl Into<Uint128>) -> Self {
+        self.config = self.config.with_validator(self_bond);
+        self
+    }
+
+    pub fn build(self) -> RunnerResult<OsmosisTestApp> {
+        Ok(OsmosisTestApp::from_base_app(BaseApp::new_with_config(
+            &self.config,
+            DEFAULT_GAS_ADJUSTMENT,
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coins, Timestamp};
+    use osmosis_std::shim::Duration;
+    use osmosis_std::types::cosmos::base::v1beta1::Coin;
+    use osmosis_std::types::cosmos::staking::v1beta1::{Params as StakingParams, Validator};
+    use osmosis_std::types::osmosis::tokenfactory::v1beta1::{
+        Params, QueryParamsRequest, QueryParamsResponse,
+    };
+    use prost::Message;
+    use serde_json::json;
+    use test_tube::{sdkerrors, Module, Runner, RunnerError};
+
+    use super::OsmosisTestAppBuilder;
+    use crate::{Account, Wasm};
+
+    // staking queries are not part of osmosis-std
+    #[derive(::prost::Message)]
+    struct StakingParamsRequest {}
+
+    #[derive(Clone, PartialEq, ::prost::Message, serde::Deserialize)]
+    struct StakingParamsResponse {
+        #[prost(message, optional, tag = "1")]
+        params: Option<StakingParams>,
+    }
+
+    #[derive(::prost::Message)]
+    struct QueryValidatorsRequest {
+        #[prost(string, tag = "1")]
+        status: String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message, serde::Deserialize)]
+    struct QueryValidatorsResponse {
+        #[prost(message, repeated, tag = "1")]
+        validators: Vec<Validator>,
+    }
+
+    #[test]
+    fn test_default_builder() {
+        let app = OsmosisTestAppBuilder::default().build().unwrap();
+        let acc = app.init_account(&coins(100_000_000_000, "uosmo")).unwrap();
+
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        Wasm::new(&app)
+            .store_code(&wasm_byte_code, None, &acc)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_gated_code_upload() {
+        let app = OsmosisTestAppBuilder::new()
+            .with_genesis(
+                "wasm",
+                json!({ "params": { "code_upload_access": { "permission": "Nobody" } } }),
+            )
+            .build()
+            .unwrap();
+        let acc = app.init_account(&coins(100_000_000_000, "uosmo")).unwrap();
+
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let err = Wasm::new(&app)
+            .store_code(&wasm_byte_code, None, &acc)
+            .unwrap_err();
+
+        assert_eq!(err.abci_code(), Some(sdkerrors::UNAUTHORIZED));
+    }
+
+    #[test]
+    fn test_custom_genesis() {
+        let genesis_time = Timestamp::from_seconds(1_600_000_000);
+        let app = OsmosisTestAppBuilder::new()
+            .with_chain_id("osmo-test-1")
+            .with_genesis_time(genesis_time)
+            .with_genesis("staking", json!({ "params": { "unbonding_time": "60s" } }))
+            .with_validator(1_000_000u128)
+            .with_validator(2_000_000u128)
+            .build()
+            .unwrap();
+
+        // first block is produced 5 seconds after genesis
+        assert_eq!(app.get_block_timestamp(), genesis_time.plus_seconds(5));
+
+        let params = app
+            .query::<StakingParamsRequest, StakingParamsResponse>(
+                "/cosmos.staking.v1beta1.Query/Params",
+                &StakingParamsRequest {},
+            )
+            .unwrap()
+            .params
+            .unwrap();
+        assert_eq!(
+            params.unbonding_time,
+            Some(Duration {
+                seconds: 60,
+                nanos: 0
+            })
+        );
+
+        let validators = app
+            .query::<QueryValidatorsRequest, QueryValidatorsResponse>(
+                "/cosmos.staking.v1beta1.Query/Validators",
+                &QueryValidatorsRequest {
+                    status: "BOND_STATUS_BONDED".to_string(),
+                },
+            )
+            .unwrap()
+            .validators;
+        let mut tokens = validators.into_iter().map(|v| v.tokens).collect::<Vec<_>>();
+        tokens.sort();
+        assert_eq!(tokens, vec!["1000000", "2000000"]);
+
+        // txs are signed with the custom chain id
+        let validator = app.get_first_validator_signing_account().unwrap();
+        let acc = app.init_account(&coins(100_000_000_000, "uosmo")).unwrap();
+        crate::Bank::new(&app)
+            .send(
+                osmosis_std::types::cosmos::bank::v1beta1::MsgSend {
+                    from_address: validator.address(),
+                    to_address: acc.address(),
+                    amount: vec![Coin {
+                        denom: "uosmo".to_string(),
+                        amount: "1".to_string(),
+                    }],
+                },
+                &validator,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_param_set_before_first_block() {
+        let denom_creation_fee = vec![Coin {
+            denom: "uosmo".to_string(),
+            amount: "42".to_string(),
+        }];
+        let app = OsmosisTestAppBuilder::new()
+            .with_param_set(
+                "tokenfactory",
+                osmosis_std::shim::Any {
+                    type_url: Params::TYPE_URL.to_string(),
+                    value: Params {
+                        denom_creation_fee: denom_creation_fee.clone(),
+                        denom_creation_gas_consume: 0,
+                    }
+                    .encode_to_vec(),
+                },
+            )
+            .build()
+            .unwrap();
+
+        let params = app
+            .query::<QueryParamsRequest, QueryParamsResponse>(
+                "/osmosis.tokenfactory.v1beta1.Query/Params",
+                &QueryParamsRequest {},
+            )
+            .unwrap()
+            .params
+            .unwrap();
+        assert_eq!(params.denom_creation_fee, denom_creation_fee);
+    }
+
+    #[test]
+    fn test_invalid_genesis() {
+        let err = OsmosisTestAppBuilder::new()
+            .with_genesis("unknown", json!({}))
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            RunnerError::ExecuteError {
+                msg: "no genesis state found for module `unknown`".to_string()
+            }
+        );
+    }
+}
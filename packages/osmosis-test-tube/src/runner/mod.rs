@@ -1,4 +1,5 @@
 pub mod app;
+pub mod builder;
 
 #[cfg(test)]
 mod tests {
@@ -7,9 +8,13 @@ mod tests {
     use crate::{Bank, Wasm};
 
     use super::app::OsmosisTestApp;
-    use cosmwasm_std::{to_binary, BankMsg, Coin, CosmosMsg, Empty, Event, WasmMsg};
+    use cosmwasm_std::{
+        attr, to_binary, BankMsg, Coin, CosmosMsg, DistributionMsg, Empty, Event, StakingMsg,
+        WasmMsg,
+    };
     use cw1_whitelist::msg::{ExecuteMsg, InstantiateMsg};
     use osmosis_std::types::cosmos::bank::v1beta1::{MsgSendResponse, QueryBalanceRequest};
+    use osmosis_std::types::cosmos::staking::v1beta1::MsgDelegateResponse;
     use osmosis_std::types::cosmwasm::wasm::v1::{
         MsgExecuteContractResponse, MsgInstantiateContractResponse,
     };
@@ -22,10 +27,32 @@ mod tests {
         MsgCreateDenom, MsgCreateDenomResponse,
     };
     use test_tube::account::Account;
-    use test_tube::runner::error::RunnerError::{ExecuteError, QueryError};
+    use test_tube::runner::error::RunnerError::{QueryError, TxError};
     use test_tube::runner::result::RawResult;
     use test_tube::runner::Runner;
-    use test_tube::{Module, RunnerExecuteResult};
+    use test_tube::{EncodeError, RunnerError};
+    use test_tube::{Module, RunnerExecuteResult, WasmContractError};
+
+    #[test]
+    fn test_parse_wasm_contract_error() {
+        assert_eq!(
+            WasmContractError::parse(
+                "failed to execute message; message index: 1: Unauthorized: execute wasm contract failed"
+            ),
+            Some(WasmContractError {
+                msg: "Unauthorized".to_string(),
+                msg_index: Some(1),
+            })
+        );
+        assert_eq!(
+            WasmContractError::parse("Generic error: not found: query wasm contract failed"),
+            Some(WasmContractError {
+                msg: "Generic error: not found".to_string(),
+                msg_index: None,
+            })
+        );
+        assert_eq!(WasmContractError::parse("insufficient funds"), None);
+    }
 
     #[derive(::prost::Message)]
     struct AdhocRandomQueryRequest {
@@ -93,13 +120,17 @@ mod tests {
             &signer,
         );
 
-        let err = res.unwrap_err();
-        assert_eq!(
-            err,
-            ExecuteError {
-                msg: String::from("pool should have at least 2 assets, as they must be swapping between at least two assets")
+        match res.unwrap_err() {
+            TxError(err) => {
+                assert_eq!(err.codespace, "gamm");
+                assert_eq!(
+                    err.log,
+                    "pool should have at least 2 assets, as they must be swapping between at least two assets"
+                );
+                assert_eq!(err.msg_index, None);
             }
-        )
+            err => panic!("unexpected error: {:?}", err),
+        }
     }
 
     #[test]
@@ -208,5 +239,159 @@ mod tests {
             create_denom_res.data.new_token_denom,
             format!("factory/{}/{}", signer.address(), denom)
         );
+
+        // StakingMsg and DistributionMsg
+        let validator = app.get_first_validator_address().unwrap();
+        let res = app
+            .execute_cosmos_msgs::<MsgDelegateResponse>(
+                &[
+                    CosmosMsg::Staking(StakingMsg::Delegate {
+                        validator: validator.clone(),
+                        amount: Coin::new(1_000_000, "uosmo"),
+                    }),
+                    CosmosMsg::Distribution(DistributionMsg::SetWithdrawAddress {
+                        address: to.address(),
+                    }),
+                    CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward {
+                        validator: validator.clone(),
+                    }),
+                ],
+                &signer,
+            )
+            .unwrap();
+        res.assert_has_event("delegate", &[("validator", &validator)]);
+
+        // BankMsg::Burn has no tx message
+        let err = app
+            .execute_cosmos_msgs::<MsgSendResponse>(
+                &[CosmosMsg::Bank(BankMsg::Burn {
+                    amount: vec![Coin::new(100, "uosmo")],
+                })],
+                &signer,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err,
+            RunnerError::EncodeError(EncodeError::UnsupportedMessage {
+                msg: "BankMsg::Burn".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_execute_response_events() {
+        let app = OsmosisTestApp::new();
+        let signer = app
+            .init_account(&[Coin::new(1_000_000_000_000, "uosmo")])
+            .unwrap();
+
+        let wasm = Wasm::new(&app);
+        let wasm_byte_code = std::fs::read("./test_artifacts/cw1_whitelist.wasm").unwrap();
+        let code_id = wasm
+            .store_code(&wasm_byte_code, None, &signer)
+            .unwrap()
+            .data
+            .code_id;
+        let contract_address = wasm
+            .instantiate(
+                code_id,
+                &InstantiateMsg {
+                    admins: vec![signer.address()],
+                    mutable: true,
+                },
+                None,
+                None,
+                &[],
+                &signer,
+            )
+            .unwrap()
+            .data
+            .address;
+
+        let res = wasm
+            .execute(
+                &contract_address,
+                &ExecuteMsg::<Empty>::Freeze {},
+                &[],
+                &signer,
+            )
+            .unwrap();
+
+        let wasm_events = res.wasm_events_for(&contract_address);
+        assert_eq!(wasm_events.len(), 1);
+        assert_eq!(wasm_events[0].attributes[1], attr("action", "freeze"));
+        assert!(res.wasm_events_for(&signer.address()).is_empty());
+
+        assert_eq!(res.find_events("wasm").len(), 1);
+        assert!(res.find_events("create_denom").is_empty());
+        assert_eq!(res.attribute("wasm", "action"), Some("freeze"));
+        assert_eq!(res.attribute("wasm", "unknown"), None);
+
+        res.assert_has_event(
+            "wasm",
+            &[
+                ("_contract_address", &contract_address),
+                ("action", "freeze"),
+            ],
+        );
+        res.assert_has_event("message", &[("sender", &signer.address())]);
+    }
+
+    #[test]
+    fn test_execute_multiple_msg_responses() {
+        use osmosis_std::types::cosmos::base::v1beta1::Coin as ProtoCoin;
+        use osmosis_std::types::osmosis::tokenfactory::v1beta1::{MsgMint, MsgMintResponse};
+
+        let app = OsmosisTestApp::default();
+        let signer = app
+            .init_account(&[Coin::new(1_000_000_000_000, "uosmo")])
+            .unwrap();
+
+        let denom = format!("factory/{}/test", signer.address());
+        let res = app
+            .execute_multiple_raw::<MsgCreateDenomResponse>(
+                vec![
+                    MsgCreateDenom {
+                        sender: signer.address(),
+                        subdenom: "test".to_string(),
+                    }
+                    .to_any()
+                    .into(),
+                    MsgMint {
+                        sender: signer.address(),
+                        amount: Some(ProtoCoin {
+                            denom: denom.clone(),
+                            amount: "100".to_string(),
+                        }),
+                        mint_to_address: signer.address(),
+                    }
+                    .to_any()
+                    .into(),
+                ],
+                &signer,
+            )
+            .unwrap();
+
+        assert_eq!(res.data.new_token_denom, denom);
+        assert_eq!(res.msg_responses().len(), 2);
+        assert_eq!(
+            res.msg_response::<MsgCreateDenomResponse>(0).unwrap(),
+            res.data
+        );
+        assert_eq!(
+            res.msg_response::<MsgMintResponse>(1).unwrap(),
+            MsgMintResponse {}
+        );
+        assert!(res.msg_response::<MsgMintResponse>(2).is_err());
+
+        let events_by_msg = res.events_by_msg();
+        assert_eq!(events_by_msg.len(), 2);
+        assert!(events_by_msg[0].iter().any(|e| e.ty == "create_denom"));
+        assert!(events_by_msg[1].iter().all(|e| e.ty != "create_denom"));
+        assert!(events_by_msg.iter().all(|events| events[0].ty == "message"));
+        assert_eq!(
+            res.attribute("create_denom", "new_token_denom"),
+            Some(denom.as_str())
+        );
     }
 }
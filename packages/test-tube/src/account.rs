@@ -1,8 +1,9 @@
 use cosmrs::{
-    crypto::{secp256k1::SigningKey, PublicKey},
+    crypto::{secp256k1::SigningKey, LegacyAminoMultisig, PublicKey},
     AccountId,
 };
 use cosmwasm_std::Coin;
+use sha2::{Digest, Sha256};
 
 pub trait Account {
     fn public_key(&self) -> PublicKey;
@@ -20,6 +21,7 @@ pub struct SigningAccount {
     prefix: String,
     signing_key: SigningKey,
     fee_setting: FeeSetting,
+    fee_granter: Option<String>,
 }
 
 impl SigningAccount {
@@ -28,15 +30,12 @@ impl SigningAccount {
             prefix,
             signing_key,
             fee_setting,
+            fee_granter: None,
         }
     }
 
     pub fn with_prefix(self, prefix: String) -> Self {
-        Self {
-            prefix,
-            signing_key: self.signing_key,
-            fee_setting: self.fee_setting,
-        }
+        Self { prefix, ..self }
     }
 
     pub fn fee_setting(&self) -> &FeeSetting {
@@ -45,9 +44,29 @@ impl SigningAccount {
 
     pub fn with_fee_setting(self, fee_setting: FeeSetting) -> Self {
         Self {
-            prefix: self.prefix,
-            signing_key: self.signing_key,
             fee_setting,
+            ..self
+        }
+    }
+
+    pub fn fee_granter(&self) -> Option<&str> {
+        self.fee_granter.as_deref()
+    }
+
+    /// Have the fees of txs paid by this account deducted from the x/feegrant allowance
+    /// given to it by `granter`.
+    pub fn with_fee_granter(self, granter: &str) -> Self {
+        Self {
+            fee_granter: Some(granter.to_string()),
+            ..self
+        }
+    }
+
+    /// Pay fees again from this account's own balance.
+    pub fn without_fee_granter(self) -> Self {
+        Self {
+            fee_granter: None,
+            ..self
         }
     }
 }
@@ -114,6 +133,141 @@ impl Account for NonSigningAccount {
     }
 }
 
+/// An x/auth multisig account, whose public key is the legacy amino multisig key made of
+/// the keys of its members. Its txs are signed by at least `threshold` of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultisigAccount {
+    prefix: String,
+    public_key: LegacyAminoMultisig,
+    fee_setting: FeeSetting,
+    fee_granter: Option<String>,
+}
+
+impl MultisigAccount {
+    /// Members keep the order of `public_keys`, which is part of the multisig address.
+    pub fn new(
+        prefix: String,
+        threshold: u32,
+        public_keys: Vec<PublicKey>,
+        fee_setting: FeeSetting,
+    ) -> Self {
+        MultisigAccount {
+            prefix,
+            public_key: LegacyAminoMultisig {
+                threshold,
+                public_keys,
+            },
+            fee_setting,
+            fee_granter: None,
+        }
+    }
+
+    pub fn with_prefix(self, prefix: String) -> Self {
+        Self { prefix, ..self }
+    }
+
+    pub fn fee_setting(&self) -> &FeeSetting {
+        &self.fee_setting
+    }
+
+    pub fn with_fee_setting(self, fee_setting: FeeSetting) -> Self {
+        Self {
+            fee_setting,
+            ..self
+        }
+    }
+
+    pub fn fee_granter(&self) -> Option<&str> {
+        self.fee_granter.as_deref()
+    }
+
+    /// Have the fees of txs from the multisig deducted from the x/feegrant allowance
+    /// given to it by `granter`.
+    pub fn with_fee_granter(self, granter: &str) -> Self {
+        Self {
+            fee_granter: Some(granter.to_string()),
+            ..self
+        }
+    }
+
+    /// Pay fees again from the multisig's own balance.
+    pub fn without_fee_granter(self) -> Self {
+        Self {
+            fee_granter: None,
+            ..self
+        }
+    }
+
+    pub fn multisig_public_key(&self) -> &LegacyAminoMultisig {
+        &self.public_key
+    }
+
+    pub fn threshold(&self) -> u32 {
+        self.public_key.threshold
+    }
+
+    /// Index of the member with the given key, if any.
+    pub fn member_index(&self, public_key: &PublicKey) -> Option<usize> {
+        self.public_key
+            .public_keys
+            .iter()
+            .position(|pk| pk == public_key)
+    }
+
+    /// Amino encoding of the multisig public key, which the address is derived from.
+    fn amino_bytes(&self) -> Vec<u8> {
+        fn put_uvarint(buf: &mut Vec<u8>, mut n: u64) {
+            while n >= 0x80 {
+                buf.push((n as u8) | 0x80);
+                n >>= 7;
+            }
+            buf.push(n as u8);
+        }
+
+        // amino prefix of `tendermint/PubKeyMultisigThreshold`
+        let mut buf = vec![0x22, 0xc1, 0xf7, 0xe2];
+
+        // field 1: threshold
+        buf.push(0x08);
+        put_uvarint(&mut buf, self.public_key.threshold.into());
+
+        for pk in &self.public_key.public_keys {
+            // amino prefix of `tendermint/PubKeySecp256k1`, then the length prefixed key
+            let key = pk.to_bytes();
+            let mut amino_pk = vec![0xeb, 0x5a, 0xe9, 0x87];
+            put_uvarint(&mut amino_pk, key.len() as u64);
+            amino_pk.extend(key);
+
+            // field 2: public keys
+            buf.push(0x12);
+            put_uvarint(&mut buf, amino_pk.len() as u64);
+            buf.extend(amino_pk);
+        }
+
+        buf
+    }
+}
+
+impl Account for MultisigAccount {
+    /// A multisig has no single key public key, this panics.
+    /// Use [`MultisigAccount::multisig_public_key`] instead.
+    fn public_key(&self) -> PublicKey {
+        panic!(
+            "multisig account `{}` has no single key public key, use `multisig_public_key`",
+            self.address()
+        )
+    }
+
+    fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn account_id(&self) -> AccountId {
+        let hash = Sha256::digest(self.amino_bytes());
+        AccountId::new(self.prefix(), &hash[..20]).expect("Prefix is constant and must valid")
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum FeeSetting {
     Auto {
@@ -209,11 +209,41 @@ fn bindgen_test_layout_GoSlice() {
     test_field_cap();
 }
 extern "C" {
-    pub fn InitTestEnv() -> GoUint64;
+    pub fn InitTestEnv(configJson: GoString) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn InitTestEnvFromGenesis(
+        configJson: GoString,
+        genesisJson: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn ExportGenesis(envId: GoUint64) -> *mut ::std::os::raw::c_char;
 }
 extern "C" {
     pub fn InitAccount(envId: GoUint64, coinsJson: GoString) -> *mut ::std::os::raw::c_char;
 }
+extern "C" {
+    pub fn InitAccounts(
+        envId: GoUint64,
+        coinsJson: GoString,
+        count: GoUint64,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn SetBalance(
+        envId: GoUint64,
+        bech32Address: GoString,
+        coinsJson: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn FundAccount(
+        envId: GoUint64,
+        bech32Address: GoString,
+        coinsJson: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
 extern "C" {
     pub fn BeginBlock(envId: GoUint64);
 }
@@ -223,9 +253,46 @@ extern "C" {
 extern "C" {
     pub fn IncreaseTime(envId: GoUint64, seconds: GoInt64);
 }
+extern "C" {
+    pub fn SkipBlocks(envId: GoUint64, n: GoUint64) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn AdvanceToNextEpoch(envId: GoUint64, identifier: GoString)
+        -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn SetBlockInterval(envId: GoUint64, nanos: GoInt64);
+}
+extern "C" {
+    pub fn SetNextBlockTime(envId: GoUint64, unixNanos: GoInt64) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn IsBlockInProgress(envId: GoUint64) -> GoUint8;
+}
 extern "C" {
     pub fn Execute(envId: GoUint64, base64ReqDeliverTx: GoString) -> *mut ::std::os::raw::c_char;
 }
+extern "C" {
+    pub fn ExecuteAs(
+        envId: GoUint64,
+        sender: GoString,
+        base64TxBodyBytes: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn ExecuteAsContract(
+        envId: GoUint64,
+        contract: GoString,
+        base64TxBodyBytes: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn WasmSudo(
+        envId: GoUint64,
+        contract: GoString,
+        msgJson: GoString,
+    ) -> *mut ::std::os::raw::c_char;
+}
 extern "C" {
     pub fn Query(
         envId: GoUint64,
@@ -272,3 +339,12 @@ extern "C" {
 extern "C" {
     pub fn CleanUp(envId: GoUint64);
 }
+extern "C" {
+    pub fn Fork(envId: GoUint64) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn Snapshot(envId: GoUint64) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn Restore(envId: GoUint64, snapshotId: GoUint64) -> *mut ::std::os::raw::c_char;
+}
@@ -1,17 +1,21 @@
 #![doc = include_str!("../README.md")]
+// `RunnerError` holds tendermint rpc errors by value and is returned everywhere,
+// boxing them would break matching on its variants
+#![allow(clippy::result_large_err)]
 
 pub mod account;
 pub mod bindings;
 mod conversions;
 pub mod module;
 pub mod runner;
+pub mod sdkerrors;
 pub mod utils;
 
 pub use cosmrs;
 
-pub use account::{Account, FeeSetting, NonSigningAccount, SigningAccount};
+pub use account::{Account, FeeSetting, MultisigAccount, NonSigningAccount, SigningAccount};
 pub use module::*;
-pub use runner::app::BaseApp;
-pub use runner::error::{DecodeError, EncodeError, RunnerError};
+pub use runner::app::{BaseApp, SnapshotId, TestEnvConfig};
+pub use runner::error::{DecodeError, EncodeError, RunnerError, TxError, WasmContractError};
 pub use runner::result::{ExecuteResponse, RunnerExecuteResult, RunnerResult};
 pub use runner::Runner;
@@ -0,0 +1,41 @@
+use crate::{fn_execute, fn_query};
+use osmosis_std::types::cosmos::feegrant::v1beta1::{
+    MsgGrantAllowance, MsgGrantAllowanceResponse, MsgRevokeAllowance, MsgRevokeAllowanceResponse,
+    QueryAllowanceRequest, QueryAllowanceResponse, QueryAllowancesRequest, QueryAllowancesResponse,
+};
+
+use crate::module::Module;
+use crate::runner::Runner;
+
+/// Wrapper of the x/feegrant module, for chains that include it.
+/// Grantees use an allowance by setting the granter with [`crate::SigningAccount::with_fee_granter`].
+pub struct Feegrant<'a, R: Runner<'a>> {
+    runner: &'a R,
+}
+
+impl<'a, R: Runner<'a>> Module<'a, R> for Feegrant<'a, R> {
+    fn new(runner: &'a R) -> Self {
+        Self { runner }
+    }
+}
+
+impl<'a, R> Feegrant<'a, R>
+where
+    R: Runner<'a>,
+{
+    fn_execute! {
+        pub grant_allowance: MsgGrantAllowance["/cosmos.feegrant.v1beta1.MsgGrantAllowance"] => MsgGrantAllowanceResponse
+    }
+
+    fn_execute! {
+        pub revoke_allowance: MsgRevokeAllowance["/cosmos.feegrant.v1beta1.MsgRevokeAllowance"] => MsgRevokeAllowanceResponse
+    }
+
+    fn_query! {
+        pub query_allowance ["/cosmos.feegrant.v1beta1.Query/Allowance"]: QueryAllowanceRequest => QueryAllowanceResponse
+    }
+
+    fn_query! {
+        pub query_allowances ["/cosmos.feegrant.v1beta1.Query/Allowances"]: QueryAllowancesRequest => QueryAllowancesResponse
+    }
+}
@@ -1,10 +1,12 @@
 use crate::runner::Runner;
 
 pub mod bank;
+pub mod feegrant;
 pub mod wasm;
 
 pub use bank::Bank;
-pub use wasm::Wasm;
+pub use feegrant::Feegrant;
+pub use wasm::{Contract, Wasm};
 
 #[macro_use]
 pub mod macros;
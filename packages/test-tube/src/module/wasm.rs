@@ -1,10 +1,21 @@
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Coin, Response, SubMsg};
+use osmosis_std::types::cosmos::base::query::v1beta1::PageRequest;
 use osmosis_std::types::cosmwasm::wasm::v1::{
-    AccessConfig, MsgExecuteContract, MsgExecuteContractResponse, MsgInstantiateContract,
-    MsgInstantiateContractResponse, MsgStoreCode, MsgStoreCodeResponse,
-    QuerySmartContractStateRequest, QuerySmartContractStateResponse,
+    AccessConfig, CodeInfoResponse, MsgClearAdmin, MsgClearAdminResponse, MsgExecuteContract,
+    MsgExecuteContractResponse, MsgInstantiateContract, MsgInstantiateContract2,
+    MsgInstantiateContract2Response, MsgInstantiateContractResponse, MsgMigrateContract,
+    MsgMigrateContractResponse, MsgStoreCode, MsgStoreCodeResponse, MsgUpdateAdmin,
+    MsgUpdateAdminResponse, QueryCodeRequest, QueryCodeResponse, QueryContractHistoryRequest,
+    QueryContractHistoryResponse, QueryContractInfoRequest, QueryContractInfoResponse,
+    QueryContractsByCodeRequest, QueryContractsByCodeResponse, QueryRawContractStateRequest,
+    QueryRawContractStateResponse, QuerySmartContractStateRequest, QuerySmartContractStateResponse,
 };
 use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::Mutex;
 
 use crate::runner::error::{DecodeError, EncodeError, RunnerError};
 use crate::runner::result::{RunnerExecuteResult, RunnerResult};
@@ -13,6 +24,58 @@ use crate::{
     runner::Runner,
 };
 
+/// Code ids of the codes stored by [`Wasm::store_code_from_file`] in an app, by checksum and
+/// requested instantiate permission. Apps clear it when their state is restored.
+#[derive(Debug, Default)]
+pub struct StoredCodes(Mutex<CodeIdsByChecksum>);
+
+type CodeIdsByChecksum = BTreeMap<[u8; 32], Vec<(Option<AccessConfig>, u64)>>;
+
+impl StoredCodes {
+    pub fn get(&self, checksum: &[u8; 32], permission: &Option<AccessConfig>) -> Option<u64> {
+        self.0
+            .lock()
+            .unwrap()
+            .get(checksum)?
+            .iter()
+            .find(|(p, _)| p == permission)
+            .map(|(_, code_id)| *code_id)
+    }
+
+    pub fn insert(&self, checksum: [u8; 32], permission: Option<AccessConfig>, code_id: u64) {
+        self.0
+            .lock()
+            .unwrap()
+            .entry(checksum)
+            .or_default()
+            .push((permission, code_id));
+    }
+
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+impl Clone for StoredCodes {
+    fn clone(&self) -> Self {
+        Self(Mutex::new(self.0.lock().unwrap().clone()))
+    }
+}
+
+impl PartialEq for StoredCodes {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other) || *self.0.lock().unwrap() == *other.0.lock().unwrap()
+    }
+}
+
+/// Response of [`Wasm::sudo`], encoded as `MsgSudoContractResponse` of later wasmd versions.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSudoContractResponse {
+    /// Data set by the contract's response.
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: Vec<u8>,
+}
+
 pub struct Wasm<'a, R: Runner<'a>> {
     runner: &'a R,
 }
@@ -44,6 +107,41 @@ where
         )
     }
 
+    /// Store the wasm code at `path`, unless the same code has already been stored in this app
+    /// with the same instantiate permission, in which case its code id is returned.
+    /// Runners that don't keep [`StoredCodes`] always store the code.
+    ///
+    /// Compiled modules live in the node home of the env, which snapshots and forks copy,
+    /// so code stored before taking a snapshot is not compiled again after restoring it.
+    pub fn store_code_from_file(
+        &self,
+        path: impl AsRef<Path>,
+        instantiate_permission: Option<AccessConfig>,
+        signer: &SigningAccount,
+    ) -> RunnerResult<MsgStoreCodeResponse> {
+        let wasm_byte_code = std::fs::read(path)?;
+        let checksum: [u8; 32] = Sha256::digest(&wasm_byte_code).into();
+
+        let stored_codes = self.runner.stored_codes();
+        if let Some(code_id) =
+            stored_codes.and_then(|codes| codes.get(&checksum, &instantiate_permission))
+        {
+            return Ok(MsgStoreCodeResponse {
+                code_id,
+                checksum: checksum.to_vec(),
+            });
+        }
+
+        let res = self
+            .store_code(&wasm_byte_code, instantiate_permission.clone(), signer)?
+            .data;
+        if let Some(codes) = stored_codes {
+            codes.insert(checksum, instantiate_permission, res.code_id);
+        }
+
+        Ok(res)
+    }
+
     pub fn instantiate<M>(
         &self,
         code_id: u64,
@@ -76,6 +174,67 @@ where
         )
     }
 
+    /// Instantiate a contract and return a [`Contract`] handle bound to its message types.
+    pub fn instantiate_contract<I, E, Q>(
+        &self,
+        code_id: u64,
+        msg: &I,
+        admin: Option<&str>,
+        label: Option<&str>,
+        funds: &[Coin],
+        signer: &SigningAccount,
+    ) -> RunnerResult<Contract<'a, R, I, E, Q>>
+    where
+        I: Serialize,
+        E: Serialize,
+        Q: Serialize,
+    {
+        let address = self
+            .instantiate(code_id, msg, admin, label, funds, signer)?
+            .data
+            .address;
+
+        Ok(Contract::new(self.runner, address, code_id))
+    }
+
+    /// Instantiate a contract at an address derived from the code checksum, the creator and
+    /// `salt`, so that it can be known before instantiation.
+    #[allow(clippy::too_many_arguments)]
+    pub fn instantiate2<M>(
+        &self,
+        code_id: u64,
+        msg: &M,
+        admin: Option<&str>,
+        label: Option<&str>,
+        funds: &[Coin],
+        salt: &[u8],
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgInstantiateContract2Response>
+    where
+        M: ?Sized + Serialize,
+    {
+        self.runner.execute(
+            MsgInstantiateContract2 {
+                sender: signer.address(),
+                admin: admin.unwrap_or_default().to_string(),
+                code_id,
+                label: label.unwrap_or(" ").to_string(), // empty string causes panic
+                msg: serde_json::to_vec(msg).map_err(EncodeError::JsonEncodeError)?,
+                funds: funds
+                    .iter()
+                    .map(|c| osmosis_std::types::cosmos::base::v1beta1::Coin {
+                        denom: c.denom.parse().unwrap(),
+                        amount: format!("{}", c.amount.u128()),
+                    })
+                    .collect(),
+                salt: salt.to_vec(),
+                fix_msg: false,
+            },
+            "/cosmwasm.wasm.v1.MsgInstantiateContract2",
+            signer,
+        )
+    }
+
     pub fn execute<M>(
         &self,
         contract: &str,
@@ -104,6 +263,60 @@ where
         )
     }
 
+    pub fn migrate<M>(
+        &self,
+        contract: &str,
+        new_code_id: u64,
+        msg: &M,
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgMigrateContractResponse>
+    where
+        M: ?Sized + Serialize,
+    {
+        self.runner.execute(
+            MsgMigrateContract {
+                sender: signer.address(),
+                contract: contract.to_owned(),
+                code_id: new_code_id,
+                msg: serde_json::to_vec(msg).map_err(EncodeError::JsonEncodeError)?,
+            },
+            "/cosmwasm.wasm.v1.MsgMigrateContract",
+            signer,
+        )
+    }
+
+    pub fn update_admin(
+        &self,
+        contract: &str,
+        new_admin: &str,
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgUpdateAdminResponse> {
+        self.runner.execute(
+            MsgUpdateAdmin {
+                sender: signer.address(),
+                new_admin: new_admin.to_owned(),
+                contract: contract.to_owned(),
+            },
+            "/cosmwasm.wasm.v1.MsgUpdateAdmin",
+            signer,
+        )
+    }
+
+    pub fn clear_admin(
+        &self,
+        contract: &str,
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgClearAdminResponse> {
+        self.runner.execute(
+            MsgClearAdmin {
+                sender: signer.address(),
+                contract: contract.to_owned(),
+            },
+            "/cosmwasm.wasm.v1.MsgClearAdmin",
+            signer,
+        )
+    }
+
     pub fn query<M, Res>(&self, contract: &str, msg: &M) -> RunnerResult<Res>
     where
         M: ?Sized + Serialize,
@@ -123,4 +336,169 @@ where
             .map_err(DecodeError::JsonDecodeError)
             .map_err(RunnerError::DecodeError)
     }
+
+    /// Execute the messages of a contract's `Response` with the contract as sender,
+    /// to check that the chain accepts them. See [`Wasm::execute_submsgs_as_contract`].
+    pub fn execute_response_as_contract(
+        &self,
+        contract: &str,
+        response: &Response,
+    ) -> RunnerExecuteResult<()> {
+        self.execute_submsgs_as_contract(contract, &response.messages)
+    }
+
+    /// Execute sub messages with the contract as sender. They are executed atomically as
+    /// plain messages: `reply_on` and `gas_limit` are ignored and no reply is dispatched.
+    /// Responses of each message are available through [`crate::ExecuteResponse::msg_response`].
+    pub fn execute_submsgs_as_contract(
+        &self,
+        contract: &str,
+        submsgs: &[SubMsg],
+    ) -> RunnerExecuteResult<()> {
+        let msgs = submsgs
+            .iter()
+            .map(|submsg| submsg.msg.clone())
+            .collect::<Vec<_>>();
+
+        self.runner.execute_cosmos_msgs_as_contract(contract, &msgs)
+    }
+
+    /// Call the `sudo` entry point of a contract, which is only reachable by the chain.
+    pub fn sudo<M>(&self, contract: &str, msg: &M) -> RunnerExecuteResult<MsgSudoContractResponse>
+    where
+        M: ?Sized + Serialize,
+    {
+        self.runner.wasm_sudo(
+            contract,
+            &serde_json::to_vec(msg).map_err(EncodeError::JsonEncodeError)?,
+        )
+    }
+
+    /// Raw value stored under `key` in the contract's storage, empty if there is none.
+    pub fn raw_query(&self, contract: &str, key: &[u8]) -> RunnerResult<Vec<u8>> {
+        let res = self
+            .runner
+            .query::<QueryRawContractStateRequest, QueryRawContractStateResponse>(
+                "/cosmwasm.wasm.v1.Query/RawContractState",
+                &QueryRawContractStateRequest {
+                    address: contract.to_owned(),
+                    query_data: key.to_vec(),
+                },
+            )?;
+
+        Ok(res.data)
+    }
+
+    pub fn contract_info(&self, contract: &str) -> RunnerResult<QueryContractInfoResponse> {
+        self.runner.query(
+            "/cosmwasm.wasm.v1.Query/ContractInfo",
+            &QueryContractInfoRequest {
+                address: contract.to_owned(),
+            },
+        )
+    }
+
+    pub fn code_info(&self, code_id: u64) -> RunnerResult<CodeInfoResponse> {
+        self.runner
+            .query::<QueryCodeRequest, QueryCodeResponse>(
+                "/cosmwasm.wasm.v1.Query/Code",
+                &QueryCodeRequest { code_id },
+            )?
+            .code_info
+            .ok_or_else(|| RunnerError::QueryError {
+                msg: format!("no code info for code id {}", code_id),
+            })
+    }
+
+    pub fn contracts_by_code(
+        &self,
+        code_id: u64,
+        pagination: Option<PageRequest>,
+    ) -> RunnerResult<QueryContractsByCodeResponse> {
+        self.runner.query(
+            "/cosmwasm.wasm.v1.Query/ContractsByCode",
+            &QueryContractsByCodeRequest {
+                code_id,
+                pagination,
+            },
+        )
+    }
+
+    pub fn contract_history(
+        &self,
+        contract: &str,
+        pagination: Option<PageRequest>,
+    ) -> RunnerResult<QueryContractHistoryResponse> {
+        self.runner.query(
+            "/cosmwasm.wasm.v1.Query/ContractHistory",
+            &QueryContractHistoryRequest {
+                address: contract.to_owned(),
+                pagination,
+            },
+        )
+    }
+}
+
+/// Handle to an instantiated contract, with its instantiate, execute and query message types
+/// bound at compile time.
+///
+/// ```rust,ignore
+/// let contract: Contract<_, InstantiateMsg, ExecuteMsg, QueryMsg> = wasm
+///     .instantiate_contract(code_id, &InstantiateMsg { .. }, None, None, &[], &signer)?;
+/// contract.execute(&ExecuteMsg::Freeze {}, &[], &signer)?;
+/// let admins: AdminListResponse = contract.query(&QueryMsg::AdminList {})?;
+/// ```
+pub struct Contract<'a, R: Runner<'a>, I, E, Q> {
+    runner: &'a R,
+    address: String,
+    code_id: u64,
+    msgs: PhantomData<(I, E, Q)>,
+}
+
+impl<'a, R, I, E, Q> Contract<'a, R, I, E, Q>
+where
+    R: Runner<'a>,
+    I: Serialize,
+    E: Serialize,
+    Q: Serialize,
+{
+    /// Handle to an already instantiated contract.
+    pub fn new(runner: &'a R, address: impl Into<String>, code_id: u64) -> Self {
+        Contract {
+            runner,
+            address: address.into(),
+            code_id,
+            msgs: PhantomData,
+        }
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn code_id(&self) -> u64 {
+        self.code_id
+    }
+
+    pub fn execute(
+        &self,
+        msg: &E,
+        funds: &[Coin],
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<MsgExecuteContractResponse> {
+        Wasm {
+            runner: self.runner,
+        }
+        .execute(&self.address, msg, funds, signer)
+    }
+
+    pub fn query<Res>(&self, msg: &Q) -> RunnerResult<Res>
+    where
+        Res: DeserializeOwned,
+    {
+        Wasm {
+            runner: self.runner,
+        }
+        .query(&self.address, msg)
+    }
 }
@@ -1,18 +1,33 @@
+use std::collections::BTreeMap;
 use std::ffi::CString;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
 
+use bip32::{DerivationPath, Language, Mnemonic};
 use cosmrs::crypto::secp256k1::SigningKey;
+use cosmrs::crypto::CompactBitArray;
+use cosmrs::proto::cosmos::crypto::multisig::v1beta1::MultiSignature;
 use cosmrs::proto::tendermint::abci::{RequestDeliverTx, ResponseDeliverTx};
-use cosmrs::tx::{Fee, SignerInfo};
-use cosmrs::{tx, Any};
-use cosmwasm_std::{Coin, Timestamp};
+use cosmrs::tx::mode_info::Multi;
+use cosmrs::tx::{Fee, ModeInfo, SignMode, SignerInfo, SignerPublicKey};
+use cosmrs::{tx, AccountId, Any};
+use cosmwasm_std::{Coin, Timestamp, Uint128};
 use prost::Message;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 
-use crate::account::{Account, FeeSetting, SigningAccount};
+use crate::account::{Account, FeeSetting, MultisigAccount, SigningAccount};
 use crate::bindings::{
-    AccountNumber, AccountSequence, BeginBlock, CleanUp, EndBlock, Execute, GetBlockHeight,
-    GetBlockTime, GetParamSet, GetValidatorAddress, GetValidatorPrivateKey, IncreaseTime,
-    InitAccount, InitTestEnv, Query, SetParamSet, Simulate,
+    AccountNumber, AccountSequence, AdvanceToNextEpoch, BeginBlock, CleanUp, EndBlock, Execute,
+    ExecuteAs, ExecuteAsContract, ExportGenesis, Fork, FundAccount, GetBlockHeight, GetBlockTime,
+    GetParamSet, GetValidatorAddress, GetValidatorPrivateKey, IncreaseTime, InitAccount,
+    InitAccounts, InitTestEnv, InitTestEnvFromGenesis, IsBlockInProgress, Query, Restore,
+    SetBalance, SetBlockInterval, SetNextBlockTime, SetParamSet, Simulate, SkipBlocks, Snapshot,
+    WasmSudo,
 };
+use crate::bindings::{GoString, GoUint64};
+use crate::module::wasm::StoredCodes;
 use crate::redefine_as_go_string;
 use crate::runner::error::{DecodeError, EncodeError, RunnerError};
 use crate::runner::result::RawResult;
@@ -21,6 +36,91 @@ use crate::runner::Runner;
 
 pub const OSMOSIS_MIN_GAS_PRICE: u128 = 2_500;
 
+/// Identifier of a chain state snapshot taken with [`BaseApp::snapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SnapshotId(u64);
+
+/// Genesis customization of the chain set up by [`BaseApp::new_with_config`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TestEnvConfig {
+    chain_id: String,
+    fee_denom: String,
+    bech32_prefix: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    genesis_time: Option<Timestamp>,
+    app_state: BTreeMap<String, serde_json::Value>,
+    param_sets: Vec<ParamSetConfig>,
+    validators: Vec<ValidatorConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ParamSetConfig {
+    subspace: String,
+    value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ValidatorConfig {
+    self_bond: Uint128,
+}
+
+impl TestEnvConfig {
+    /// `fee_denom` replaces the sdk default denom `stake` in genesis, so it is also the bond denom.
+    ///
+    /// The bech32 prefix is process global on the chain side, so apps with different
+    /// prefixes can't be used at the same time: creating an app with another prefix than
+    /// the one of the apps alive in the process fails.
+    pub fn new(chain_id: &str, fee_denom: &str, bech32_prefix: &str) -> Self {
+        Self {
+            chain_id: chain_id.to_string(),
+            fee_denom: fee_denom.to_string(),
+            bech32_prefix: bech32_prefix.to_string(),
+            genesis_time: None,
+            app_state: BTreeMap::new(),
+            param_sets: vec![],
+            validators: vec![],
+        }
+    }
+
+    pub fn with_chain_id(mut self, chain_id: &str) -> Self {
+        self.chain_id = chain_id.to_string();
+        self
+    }
+
+    /// Set the genesis time, defaults to the current time.
+    pub fn with_genesis_time(mut self, genesis_time: Timestamp) -> Self {
+        self.genesis_time = Some(genesis_time);
+        self
+    }
+
+    /// Override the genesis state of a module. `state` is deep merged into the
+    /// default genesis state: objects are merged key by key while any other value,
+    /// arrays included, replaces the default one.
+    /// Overriding the same module again replaces the previous override.
+    pub fn with_genesis(mut self, module: &str, state: serde_json::Value) -> Self {
+        self.app_state.insert(module.to_string(), state);
+        self
+    }
+
+    /// Set parameter set for a given subspace right after genesis, before the first block.
+    pub fn with_param_set(mut self, subspace: &str, pset: impl Into<Any>) -> Self {
+        self.param_sets.push(ParamSetConfig {
+            subspace: subspace.to_string(),
+            value: base64::encode(Message::encode_to_vec(&pset.into())),
+        });
+        self
+    }
+
+    /// Add a bonded validator with the given self bond, in bond denom, to the initial
+    /// validator set. A single validator is created if none is added.
+    pub fn with_validator(mut self, self_bond: impl Into<Uint128>) -> Self {
+        self.validators.push(ValidatorConfig {
+            self_bond: self_bond.into(),
+        });
+        self
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct BaseApp {
     id: u64,
@@ -28,6 +128,7 @@ pub struct BaseApp {
     chain_id: String,
     address_prefix: String,
     default_gas_adjustment: f64,
+    stored_codes: StoredCodes,
 }
 
 impl BaseApp {
@@ -37,23 +138,260 @@ impl BaseApp {
         address_prefix: &str,
         default_gas_adjustment: f64,
     ) -> Self {
-        let id = unsafe { InitTestEnv() };
-        BaseApp {
-            id,
-            fee_denom: fee_denom.to_string(),
-            chain_id: chain_id.to_string(),
-            address_prefix: address_prefix.to_string(),
+        Self::new_with_config(
+            &TestEnvConfig::new(chain_id, fee_denom, address_prefix),
             default_gas_adjustment,
-        }
+        )
+        .expect("default config must be valid")
+    }
+
+    /// Create a new app with customized genesis. Fails if the genesis state overrides
+    /// or param sets in `config` are invalid.
+    pub fn new_with_config(
+        config: &TestEnvConfig,
+        default_gas_adjustment: f64,
+    ) -> RunnerResult<Self> {
+        let config_json = serde_json::to_string(config).map_err(EncodeError::JsonEncodeError)?;
+
+        let res = unsafe {
+            redefine_as_go_string!(config_json);
+            let res = InitTestEnv(config_json);
+            RawResult::from_non_null_ptr(res).into_result()?
+        };
+
+        Ok(BaseApp {
+            id: decode_u64(&res),
+            fee_denom: config.fee_denom.clone(),
+            chain_id: config.chain_id.clone(),
+            address_prefix: config.bech32_prefix.clone(),
+            default_gas_adjustment,
+            stored_codes: StoredCodes::default(),
+        })
+    }
+
+    /// Boot a new app from a genesis JSON file, such as one written by `export_genesis`.
+    /// The chain id is taken from the genesis file and the first block is produced
+    /// at its `initial_height`.
+    ///
+    /// Validator private keys are not part of genesis, so the validators of an app
+    /// booted this way can't be used as signing accounts.
+    pub fn from_genesis(
+        path: impl AsRef<Path>,
+        fee_denom: &str,
+        address_prefix: &str,
+        default_gas_adjustment: f64,
+    ) -> RunnerResult<Self> {
+        let genesis = fs::read_to_string(path)?;
+
+        let chain_id = serde_json::from_str::<serde_json::Value>(&genesis)
+            .map_err(DecodeError::JsonDecodeError)?
+            .get("chain_id")
+            .and_then(|chain_id| chain_id.as_str())
+            .ok_or_else(|| RunnerError::GenericError("genesis has no `chain_id`".to_string()))?
+            .to_string();
+
+        let config = TestEnvConfig::new(&chain_id, fee_denom, address_prefix);
+        let config_json = serde_json::to_string(&config).map_err(EncodeError::JsonEncodeError)?;
+
+        let res = unsafe {
+            redefine_as_go_string!(config_json);
+            redefine_as_go_string!(genesis);
+            let res = InitTestEnvFromGenesis(config_json, genesis);
+            RawResult::from_non_null_ptr(res).into_result()?
+        };
+
+        Ok(BaseApp {
+            id: decode_u64(&res),
+            fee_denom: config.fee_denom,
+            chain_id: config.chain_id,
+            address_prefix: config.bech32_prefix,
+            default_gas_adjustment,
+            stored_codes: StoredCodes::default(),
+        })
+    }
+
+    /// Export the committed chain state as a genesis JSON file.
+    /// An app booted from it with `from_genesis` continues from the next block height.
+    pub fn export_genesis(&self, path: impl AsRef<Path>) -> RunnerResult<()> {
+        let genesis = unsafe {
+            let res = ExportGenesis(self.id);
+            RawResult::from_non_null_ptr(res).into_result()?
+        };
+
+        fs::write(path, genesis)?;
+
+        Ok(())
     }
 
     /// Increase the time of the blockchain by the given number of seconds.
+    /// Panics if a block is in progress.
     pub fn increase_time(&self, seconds: u64) {
+        assert!(
+            !self.is_block_in_progress(),
+            "cannot increase time while a block is in progress"
+        );
         unsafe {
             IncreaseTime(self.id, seconds.try_into().unwrap());
         }
     }
 
+    /// Produce `n` empty blocks, each one block interval after the previous one.
+    pub fn skip_blocks(&self, n: u64) -> RunnerResult<()> {
+        unsafe {
+            let res = SkipBlocks(self.id, n);
+
+            // returns empty bytes if success
+            RawResult::from_non_null_ptr(res).into_result()?;
+        }
+
+        Ok(())
+    }
+
+    /// Produce a single block at the time the next epoch of the epochs module with
+    /// the given identifier starts, so that its epoch hooks run.
+    pub fn advance_to_next_epoch(&self, identifier: &str) -> RunnerResult<()> {
+        redefine_as_go_string!(identifier);
+
+        unsafe {
+            let res = AdvanceToNextEpoch(self.id, identifier);
+
+            // returns empty bytes if success
+            RawResult::from_non_null_ptr(res).into_result()?;
+        }
+
+        Ok(())
+    }
+
+    /// Set the time between blocks, 5 seconds by default.
+    pub fn set_block_interval(&self, interval: Duration) {
+        let nanos = interval
+            .as_nanos()
+            .try_into()
+            .expect("block interval must fit in i64 nanoseconds");
+        unsafe { SetBlockInterval(self.id, nanos) }
+    }
+
+    /// Set the time of the next block, e.g. the one including the next tx,
+    /// instead of advancing by the block interval. `time` must be after the current block time.
+    /// `increase_time` discards it.
+    pub fn set_block_time(&self, time: Timestamp) -> RunnerResult<()> {
+        let nanos = time
+            .nanos()
+            .try_into()
+            .map_err(|_| RunnerError::GenericError(format!("block time out of range: {}", time)))?;
+
+        unsafe {
+            let res = SetNextBlockTime(self.id, nanos);
+
+            // returns empty bytes if success
+            RawResult::from_non_null_ptr(res).into_result()?;
+        }
+
+        Ok(())
+    }
+
+    /// Begin a new block. Until `end_block_and_commit` is called, every tx executed on
+    /// this app, through `deliver_tx`, `Runner` or module wrappers, as well as `init_account`,
+    /// lands in this block instead of producing a block of its own.
+    ///
+    /// Fees for signers with `FeeSetting::Auto` are estimated against the state committed by
+    /// the previous block, so such a signer can only send one tx per block.
+    /// Use `FeeSetting::Custom` to send several txs from the same signer in one block.
+    pub fn begin_block(&self) -> RunnerResult<()> {
+        if self.is_block_in_progress() {
+            return Err(RunnerError::GenericError(
+                "block is already in progress".to_string(),
+            ));
+        }
+
+        unsafe { BeginBlock(self.id) };
+
+        Ok(())
+    }
+
+    /// Deliver a tx to the block in progress, see `begin_block`.
+    pub fn deliver_tx<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signer: &SigningAccount,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        if !self.is_block_in_progress() {
+            return Err(RunnerError::GenericError(
+                "no block in progress, call `begin_block` first".to_string(),
+            ));
+        }
+
+        self.execute_tx(msgs, &[signer.into()], signer.into())
+    }
+
+    /// End the block in progress and commit its state.
+    pub fn end_block_and_commit(&self) -> RunnerResult<()> {
+        if !self.is_block_in_progress() {
+            return Err(RunnerError::GenericError(
+                "no block in progress, call `begin_block` first".to_string(),
+            ));
+        }
+
+        unsafe { EndBlock(self.id) };
+
+        Ok(())
+    }
+
+    /// Whether a block begun with `begin_block` has not ended yet.
+    pub fn is_block_in_progress(&self) -> bool {
+        unsafe { IsBlockInProgress(self.id) != 0 }
+    }
+
+    /// Create an independent copy of the test environment from its current state.
+    /// Both apps can diverge freely and each one is cleaned up when dropped.
+    /// Snapshots taken from this app are not carried over to the fork.
+    pub fn fork(&self) -> RunnerResult<Self> {
+        let res = unsafe {
+            let res = Fork(self.id);
+            RawResult::from_non_null_ptr(res).into_result()?
+        };
+
+        Ok(BaseApp {
+            id: decode_u64(&res),
+            fee_denom: self.fee_denom.clone(),
+            chain_id: self.chain_id.clone(),
+            address_prefix: self.address_prefix.clone(),
+            default_gas_adjustment: self.default_gas_adjustment,
+            // the forked chain holds the same codes
+            stored_codes: self.stored_codes.clone(),
+        })
+    }
+
+    /// Take a snapshot of the current chain state, which can later be restored
+    /// with `restore` any number of times. Snapshots live as long as the app does.
+    pub fn snapshot(&self) -> RunnerResult<SnapshotId> {
+        let res = unsafe {
+            let res = Snapshot(self.id);
+            RawResult::from_non_null_ptr(res).into_result()?
+        };
+
+        Ok(SnapshotId(decode_u64(&res)))
+    }
+
+    /// Restore chain state to the given snapshot.
+    /// Everything that happened after the snapshot was taken is discarded.
+    pub fn restore(&self, snapshot: SnapshotId) -> RunnerResult<()> {
+        unsafe {
+            let res = Restore(self.id, snapshot.0);
+
+            // returns empty bytes if success
+            RawResult::from_non_null_ptr(res).into_result()?;
+        }
+
+        // codes stored after the snapshot are gone
+        self.stored_codes.clear();
+
+        Ok(())
+    }
+
     /// Get the first validator address
     pub fn get_first_validator_address(&self) -> RunnerResult<String> {
         let addr = unsafe {
@@ -67,30 +405,151 @@ impl BaseApp {
         Ok(addr)
     }
 
-    /// Get the first validator signing account
+    /// Get the first validator signing account, fails if the env does not know the validator
+    /// private key, like envs booted from genesis.
     pub fn get_first_validator_signing_account(&self) -> RunnerResult<SigningAccount> {
-        let base64_priv = unsafe {
+        let secp256k1_priv = unsafe {
             let val_priv = GetValidatorPrivateKey(self.id, 0);
-            CString::from_raw(val_priv)
-        }
-        .to_str()
-        .map_err(DecodeError::Utf8Error)?
-        .to_string();
+            RawResult::from_non_null_ptr(val_priv).into_result()?
+        };
 
-        let secp256k1_priv = base64::decode(base64_priv).map_err(DecodeError::Base64DecodeError)?;
         let signging_key = SigningKey::from_bytes(&secp256k1_priv).map_err(|e| {
             let msg = e.to_string();
             DecodeError::SigningKeyDecodeError { msg }
         })?;
 
-        Ok(SigningAccount::new(
+        Ok(self.signing_account(signging_key))
+    }
+
+    /// Initialize account with initial balance of any coins, like [`BaseApp::init_account`],
+    /// but with a private key derived from `seed`. The same seed always gives the same address,
+    /// which keeps contract addresses and events stable across runs.
+    pub fn init_account_with_seed(
+        &self,
+        seed: &[u8],
+        coins: &[Coin],
+    ) -> RunnerResult<SigningAccount> {
+        let signing_key = SigningKey::from_bytes(&Sha256::digest(seed)).map_err(|e| {
+            let msg = e.to_string();
+            DecodeError::SigningKeyDecodeError { msg }
+        })?;
+
+        self.fund_signing_account(signing_key, coins)
+    }
+
+    /// Initialize account with initial balance of any coins, like [`BaseApp::init_account`],
+    /// but with the private key of a BIP-39 `mnemonic` at `hd_path`, e.g. `m/44'/118'/0'/0/0`.
+    pub fn init_account_from_mnemonic(
+        &self,
+        mnemonic: &str,
+        hd_path: &str,
+        coins: &[Coin],
+    ) -> RunnerResult<SigningAccount> {
+        let to_decode_err =
+            |e: bip32::Error| DecodeError::SigningKeyDecodeError { msg: e.to_string() };
+
+        let seed = Mnemonic::new(mnemonic, Language::English)
+            .map_err(to_decode_err)?
+            .to_seed("");
+        let path = hd_path.parse::<DerivationPath>().map_err(to_decode_err)?;
+        let signing_key = SigningKey::derive_from_path(seed, &path).map_err(to_decode_err)?;
+
+        self.fund_signing_account(signing_key, coins)
+    }
+
+    /// Initialize a multisig account of `members` with initial balance of any coins.
+    /// Members keep their order, which is part of the multisig address.
+    pub fn init_multisig_account(
+        &self,
+        members: &[&SigningAccount],
+        threshold: u32,
+        coins: &[Coin],
+    ) -> RunnerResult<MultisigAccount> {
+        let account = MultisigAccount::new(
             self.address_prefix.clone(),
-            signging_key,
-            FeeSetting::Auto {
-                gas_price: Coin::new(OSMOSIS_MIN_GAS_PRICE, self.fee_denom.clone()),
-                gas_adjustment: self.default_gas_adjustment,
-            },
-        ))
+            threshold,
+            members.iter().map(|member| member.public_key()).collect(),
+            self.default_fee_setting(),
+        );
+
+        unsafe { self.run_block(|| self.mint_to(&account.address(), coins))? };
+
+        Ok(account)
+    }
+
+    fn fund_signing_account(
+        &self,
+        signing_key: SigningKey,
+        coins: &[Coin],
+    ) -> RunnerResult<SigningAccount> {
+        let account = self.signing_account(signing_key);
+
+        unsafe { self.run_block(|| self.mint_to(&account.address(), coins))? };
+
+        Ok(account)
+    }
+
+    /// Mint `coins` to any address, contracts and module accounts included.
+    ///
+    /// No block is produced for it: while a block is in progress the coins are minted in that
+    /// block, otherwise they are committed along with the next block. Until then the app
+    /// can't be snapshotted or forked.
+    pub fn mint_to(&self, address: &str, coins: &[Coin]) -> RunnerResult<()> {
+        let coins_json = Self::sorted_coins_json(coins)?;
+        redefine_as_go_string!(coins_json);
+        redefine_as_go_string!(address);
+
+        unsafe {
+            let res = FundAccount(self.id, address, coins_json);
+
+            // returns empty bytes if success
+            RawResult::from_non_null_ptr(res).into_result()?;
+        }
+
+        Ok(())
+    }
+
+    /// Mint or burn coins of any address until its balance is exactly `coins`.
+    /// Denoms held by the address but missing from `coins` are burned entirely.
+    ///
+    /// Like [`BaseApp::mint_to`], no block is produced for it.
+    pub fn set_balance(&self, address: &str, coins: &[Coin]) -> RunnerResult<()> {
+        let coins_json = Self::sorted_coins_json(coins)?;
+        redefine_as_go_string!(coins_json);
+        redefine_as_go_string!(address);
+
+        unsafe {
+            let res = SetBalance(self.id, address, coins_json);
+
+            // returns empty bytes if success
+            RawResult::from_non_null_ptr(res).into_result()?;
+        }
+
+        Ok(())
+    }
+
+    fn sorted_coins_json(coins: &[Coin]) -> RunnerResult<String> {
+        let mut coins = coins.to_vec();
+
+        // invalid coins if denom are unsorted
+        coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+        Ok(serde_json::to_string(&coins).map_err(EncodeError::JsonEncodeError)?)
+    }
+
+    fn signing_account(&self, signing_key: SigningKey) -> SigningAccount {
+        SigningAccount::new(
+            self.address_prefix.clone(),
+            signing_key,
+            self.default_fee_setting(),
+        )
+    }
+
+    fn default_fee_setting(&self) -> FeeSetting {
+        FeeSetting::Auto {
+            gas_price: Coin::new(OSMOSIS_MIN_GAS_PRICE, self.fee_denom.clone()),
+            gas_adjustment: self.default_gas_adjustment,
+        }
     }
 
     /// Get the current block time
@@ -121,10 +580,10 @@ impl BaseApp {
         redefine_as_go_string!(coins_json);
 
         let base64_priv = unsafe {
-            BeginBlock(self.id);
-            let addr = InitAccount(self.id, coins_json);
-            EndBlock(self.id);
-            CString::from_raw(addr)
+            self.run_block(|| {
+                let addr = InitAccount(self.id, coins_json);
+                Ok::<_, RunnerError>(CString::from_raw(addr))
+            })?
         }
         .to_str()
         .map_err(DecodeError::Utf8Error)?
@@ -146,52 +605,99 @@ impl BaseApp {
         ))
     }
     /// Convinience function to create multiple accounts with the same
-    /// Initial coins balance
+    /// Initial coins balance. All of them are created in a single block.
     pub fn init_accounts(&self, coins: &[Coin], count: u64) -> RunnerResult<Vec<SigningAccount>> {
-        (0..count).map(|_| self.init_account(coins)).collect()
+        let coins_json = Self::sorted_coins_json(coins)?;
+        redefine_as_go_string!(coins_json);
+
+        // private keys of all accounts, concatenated
+        let secp256k1_privs = unsafe {
+            self.run_block(|| {
+                let res = InitAccounts(self.id, coins_json, count);
+                RawResult::from_non_null_ptr(res).into_result()
+            })?
+        };
+
+        secp256k1_privs
+            .chunks(32)
+            .map(|secp256k1_priv| {
+                let signing_key = SigningKey::from_bytes(secp256k1_priv).map_err(|e| {
+                    let msg = e.to_string();
+                    DecodeError::SigningKeyDecodeError { msg }
+                })?;
+                Ok(self.signing_account(signing_key))
+            })
+            .collect()
     }
 
+    /// Sign a tx with `signers`, in the order the messages require their signatures.
+    /// `fee_payer` signs last if it is not one of them.
     fn create_signed_tx<I>(
         &self,
         msgs: I,
-        signer: &SigningAccount,
-        fee: Fee,
+        signers: &[TxSigner],
+        fee_payer: TxSigner,
+        mut fee: Fee,
     ) -> RunnerResult<Vec<u8>>
     where
         I: IntoIterator<Item = cosmrs::Any>,
     {
+        let fee_payer_id = fee_payer.account_id();
+
+        let mut signers = signers.to_vec();
+        if !signers.iter().any(|s| s.account_id() == fee_payer_id) {
+            signers.push(fee_payer);
+        }
+
+        // the first signer pays the fee unless told otherwise
+        if signers[0].account_id() != fee_payer_id {
+            fee.payer = Some(fee_payer_id);
+        }
+
+        if let Some(granter) = fee_payer.fee_granter() {
+            fee.granter = Some(granter.parse().map_err(|e| {
+                RunnerError::GenericError(format!("invalid fee granter `{}`: {}", granter, e))
+            })?);
+        }
+
         let tx_body = tx::Body::new(msgs, "", 0u32);
-        let addr = signer.address();
-        redefine_as_go_string!(addr);
-
-        let seq = unsafe { AccountSequence(self.id, addr) };
-
-        let account_number = unsafe { AccountNumber(self.id, addr) };
-        let signer_info = SignerInfo::single_direct(Some(signer.public_key()), seq);
-        let auth_info = signer_info.auth_info(fee);
-        let sign_doc = tx::SignDoc::new(
-            &tx_body,
-            &auth_info,
-            &(self
-                .chain_id
-                .parse()
-                .expect("parse const str of chain id should never fail")),
-            account_number,
-        )
-        .map_err(|e| match e.downcast::<prost::EncodeError>() {
+        let chain_id = self
+            .chain_id
+            .parse()
+            .expect("parse const str of chain id should never fail");
+
+        let mut signer_infos = Vec::with_capacity(signers.len());
+        let mut account_numbers = Vec::with_capacity(signers.len());
+        for signer in &signers {
+            let addr = signer.account_id().to_string();
+            redefine_as_go_string!(addr);
+
+            let seq = unsafe { AccountSequence(self.id, addr) };
+            let account_number = unsafe { AccountNumber(self.id, addr) };
+
+            signer_infos.push(signer.signer_info(seq)?);
+            account_numbers.push(account_number);
+        }
+        let auth_info = tx::AuthInfo { signer_infos, fee };
+
+        let to_encode_err = |e: cosmrs::ErrorReport| match e.downcast::<prost::EncodeError>() {
             Ok(encode_err) => EncodeError::ProtoEncodeError(encode_err),
             Err(e) => panic!("expect `prost::EncodeError` but got {:?}", e),
-        })?;
+        };
 
-        let tx_raw = sign_doc.sign(signer.signing_key()).unwrap();
+        let mut tx_raw = cosmrs::proto::cosmos::tx::v1beta1::TxRaw::default();
+        for (signer, account_number) in signers.iter().zip(account_numbers) {
+            let sign_doc = tx::SignDoc::new(&tx_body, &auth_info, &chain_id, account_number)
+                .map_err(to_encode_err)?;
 
-        tx_raw
-            .to_bytes()
-            .map_err(|e| match e.downcast::<prost::EncodeError>() {
-                Ok(encode_err) => EncodeError::ProtoEncodeError(encode_err),
-                Err(e) => panic!("expect `prost::EncodeError` but got {:?}", e),
-            })
-            .map_err(RunnerError::EncodeError)
+            tx_raw.body_bytes = sign_doc.body_bytes.clone();
+            tx_raw.auth_info_bytes = sign_doc.auth_info_bytes.clone();
+
+            let sign_doc_bytes = sign_doc.into_bytes().map_err(to_encode_err)?;
+            tx_raw.signatures.push(signer.sign(&sign_doc_bytes)?);
+        }
+
+        Ok(tx_raw.encode_to_vec())
     }
 
     pub fn simulate_tx<I>(
@@ -199,6 +705,18 @@ impl BaseApp {
         msgs: I,
         signer: &SigningAccount,
     ) -> RunnerResult<cosmrs::proto::cosmos::base::abci::v1beta1::GasInfo>
+    where
+        I: IntoIterator<Item = cosmrs::Any>,
+    {
+        self.simulate_multi_signer_tx(msgs, &[signer.into()], signer.into())
+    }
+
+    fn simulate_multi_signer_tx<I>(
+        &self,
+        msgs: I,
+        signers: &[TxSigner],
+        fee_payer: TxSigner,
+    ) -> RunnerResult<cosmrs::proto::cosmos::base::abci::v1beta1::GasInfo>
     where
         I: IntoIterator<Item = cosmrs::Any>,
     {
@@ -210,7 +728,7 @@ impl BaseApp {
             0u64,
         );
 
-        let tx = self.create_signed_tx(msgs, signer, zero_fee)?;
+        let tx = self.create_signed_tx(msgs, signers, fee_payer, zero_fee)?;
         let base64_tx_bytes = base64::encode(tx);
         redefine_as_go_string!(base64_tx_bytes);
 
@@ -223,16 +741,21 @@ impl BaseApp {
                 .map_err(RunnerError::DecodeError)
         }
     }
-    fn estimate_fee<I>(&self, msgs: I, signer: &SigningAccount) -> RunnerResult<Fee>
+    fn estimate_fee<I>(
+        &self,
+        msgs: I,
+        signers: &[TxSigner],
+        fee_payer: TxSigner,
+    ) -> RunnerResult<Fee>
     where
         I: IntoIterator<Item = cosmrs::Any>,
     {
-        match &signer.fee_setting() {
+        match &fee_payer.fee_setting() {
             FeeSetting::Auto {
                 gas_price,
                 gas_adjustment,
             } => {
-                let gas_info = self.simulate_tx(msgs, signer)?;
+                let gas_info = self.simulate_multi_signer_tx(msgs, signers, fee_payer)?;
                 let gas_limit = ((gas_info.gas_used as f64) * (gas_adjustment)).ceil() as u64;
 
                 let amount = cosmrs::Coin {
@@ -249,9 +772,85 @@ impl BaseApp {
         }
     }
 
+    /// Sign and deliver a tx, a block must be in progress.
+    fn execute_tx<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signers: &[TxSigner],
+        fee_payer: TxSigner,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        let fee = match &fee_payer.fee_setting() {
+            FeeSetting::Auto { .. } => self.estimate_fee(msgs.clone(), signers, fee_payer)?,
+            FeeSetting::Custom { amount, gas_limit } => Fee::from_amount_and_gas(
+                cosmrs::Coin {
+                    denom: amount.denom.parse().unwrap(),
+                    amount: amount.amount.to_string().parse().unwrap(),
+                },
+                *gas_limit,
+            ),
+        };
+
+        let tx = self.create_signed_tx(msgs, signers, fee_payer, fee)?;
+
+        let mut buf = Vec::new();
+        RequestDeliverTx::encode(&RequestDeliverTx { tx }, &mut buf)
+            .map_err(EncodeError::ProtoEncodeError)?;
+
+        let base64_req = base64::encode(buf);
+        redefine_as_go_string!(base64_req);
+
+        unsafe {
+            let res = Execute(self.id, base64_req);
+            let res = RawResult::from_non_null_ptr(res).into_result()?;
+
+            ResponseDeliverTx::decode(res.as_slice())
+                .map_err(DecodeError::ProtoDecodeError)?
+                .try_into()
+        }
+    }
+
+    /// Execute `msgs` as `sender` through `execute`, one of the Go exports taking
+    /// a proto encoded `TxBody` in place of a signed tx.
+    unsafe fn execute_tx_body<R>(
+        &self,
+        execute: unsafe extern "C" fn(GoUint64, GoString, GoString) -> *mut std::os::raw::c_char,
+        sender: &str,
+        msgs: Vec<cosmrs::Any>,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        let tx_body = cosmrs::proto::cosmos::tx::v1beta1::TxBody {
+            messages: msgs,
+            ..Default::default()
+        };
+        let base64_tx_body = base64::encode(tx_body.encode_to_vec());
+        redefine_as_go_string!(sender);
+        redefine_as_go_string!(base64_tx_body);
+
+        unsafe {
+            self.run_block(|| {
+                let res = execute(self.id, sender, base64_tx_body);
+                let res = RawResult::from_non_null_ptr(res).into_result()?;
+
+                ResponseDeliverTx::decode(res.as_slice())
+                    .map_err(DecodeError::ProtoDecodeError)?
+                    .try_into()
+            })
+        }
+    }
+
     /// Ensure that all execution that happens in `execution` happens in a block
     /// and end block properly, no matter it suceeds or fails.
+    /// If a block is already in progress, `execution` becomes part of it.
     unsafe fn run_block<T, E>(&self, execution: impl Fn() -> Result<T, E>) -> Result<T, E> {
+        if self.is_block_in_progress() {
+            return execution();
+        }
+
         unsafe { BeginBlock(self.id) };
         match execution() {
             ok @ Ok(_) => {
@@ -267,19 +866,19 @@ impl BaseApp {
 
     /// Set parameter set for a given subspace.
     pub fn set_param_set(&self, subspace: &str, pset: impl Into<Any>) -> RunnerResult<()> {
-        unsafe {
-            BeginBlock(self.id);
-            let pset = Message::encode_to_vec(&pset.into());
-            let pset = base64::encode(pset);
-            redefine_as_go_string!(pset);
-            redefine_as_go_string!(subspace);
-            let res = SetParamSet(self.id, subspace, pset);
+        let pset = Message::encode_to_vec(&pset.into());
+        let pset = base64::encode(pset);
+        redefine_as_go_string!(pset);
+        redefine_as_go_string!(subspace);
 
-            EndBlock(self.id);
+        unsafe {
+            self.run_block(|| {
+                let res = SetParamSet(self.id, subspace, pset);
 
-            // returns empty bytes if success
-            RawResult::from_non_null_ptr(res).into_result()?;
-            Ok(())
+                // returns empty bytes if success
+                RawResult::from_non_null_ptr(res).into_result()?;
+                Ok(())
+            })
         }
     }
 
@@ -300,6 +899,130 @@ impl BaseApp {
     }
 }
 
+/// Decode big-endian encoded `u64` returned from the Go side, eg. ids.
+fn decode_u64(bytes: &[u8]) -> u64 {
+    u64::from_be_bytes(
+        bytes
+            .try_into()
+            .expect("Go code must encode u64 as 8 bytes"),
+    )
+}
+
+/// A signer of a tx built by [`BaseApp`].
+#[derive(Clone, Copy)]
+enum TxSigner<'a> {
+    Single(&'a SigningAccount),
+    /// A multisig account along with the members signing for it.
+    Multisig(&'a MultisigAccount, &'a [&'a SigningAccount]),
+}
+
+impl<'a> From<&'a SigningAccount> for TxSigner<'a> {
+    fn from(signer: &'a SigningAccount) -> Self {
+        TxSigner::Single(signer)
+    }
+}
+
+impl TxSigner<'_> {
+    fn account_id(&self) -> AccountId {
+        match self {
+            TxSigner::Single(signer) => signer.account_id(),
+            TxSigner::Multisig(multisig, _) => multisig.account_id(),
+        }
+    }
+
+    fn fee_setting(&self) -> &FeeSetting {
+        match self {
+            TxSigner::Single(signer) => signer.fee_setting(),
+            TxSigner::Multisig(multisig, _) => multisig.fee_setting(),
+        }
+    }
+
+    fn fee_granter(&self) -> Option<&str> {
+        match self {
+            TxSigner::Single(signer) => signer.fee_granter(),
+            TxSigner::Multisig(multisig, _) => multisig.fee_granter(),
+        }
+    }
+
+    fn signer_info(&self, sequence: u64) -> RunnerResult<SignerInfo> {
+        match self {
+            TxSigner::Single(signer) => Ok(SignerInfo::single_direct(
+                Some(signer.public_key()),
+                sequence,
+            )),
+            TxSigner::Multisig(multisig, _) => {
+                let members = self.multisig_members()?;
+
+                // bit `i` is set if the member at index `i` signs, most significant bit first
+                let n = multisig.multisig_public_key().public_keys.len();
+                let mut elems = vec![0u8; n.div_ceil(8)];
+                for (i, _) in &members {
+                    elems[i / 8] |= 1 << (7 - i % 8);
+                }
+
+                Ok(SignerInfo {
+                    public_key: Some(SignerPublicKey::LegacyAminoMultisig(
+                        multisig.multisig_public_key().clone(),
+                    )),
+                    mode_info: ModeInfo::Multi(Multi {
+                        bitarray: CompactBitArray::new((n % 8) as u32, elems),
+                        mode_infos: vec![ModeInfo::single(SignMode::Direct); members.len()],
+                    }),
+                    sequence,
+                })
+            }
+        }
+    }
+
+    fn sign(&self, sign_doc_bytes: &[u8]) -> RunnerResult<Vec<u8>> {
+        let sign = |signer: &SigningAccount| {
+            let signature = signer.signing_key().sign(sign_doc_bytes).unwrap();
+            signature.as_ref().to_vec()
+        };
+
+        match self {
+            TxSigner::Single(signer) => Ok(sign(signer)),
+            TxSigner::Multisig(..) => {
+                let signatures = self
+                    .multisig_members()?
+                    .into_iter()
+                    .map(|(_, member)| sign(member))
+                    .collect();
+
+                Ok(MultiSignature { signatures }.encode_to_vec())
+            }
+        }
+    }
+
+    /// Members signing for a multisig along with their index, in the order of the multisig keys.
+    fn multisig_members(&self) -> RunnerResult<Vec<(usize, &SigningAccount)>> {
+        let (multisig, signers) = match self {
+            TxSigner::Single(_) => return Ok(vec![]),
+            TxSigner::Multisig(multisig, signers) => (multisig, signers),
+        };
+
+        let mut members = signers
+            .iter()
+            .map(|&signer| {
+                multisig
+                    .member_index(&signer.public_key())
+                    .map(|i| (i, signer))
+                    .ok_or_else(|| {
+                        RunnerError::GenericError(format!(
+                            "`{}` is not a member of multisig `{}`",
+                            signer.address(),
+                            multisig.address()
+                        ))
+                    })
+            })
+            .collect::<RunnerResult<Vec<_>>>()?;
+        members.sort_by_key(|(i, _)| *i);
+        members.dedup_by_key(|(i, _)| *i);
+
+        Ok(members)
+    }
+}
+
 /// Cleanup the test environment when the app is dropped.
 impl Drop for BaseApp {
     fn drop(&mut self) {
@@ -343,29 +1066,64 @@ impl<'a> Runner<'a> for BaseApp {
     where
         R: ::prost::Message + Default,
     {
-        unsafe {
-            self.run_block(|| {
-                let fee = match &signer.fee_setting() {
-                    FeeSetting::Auto { .. } => self.estimate_fee(msgs.clone(), signer)?,
-                    FeeSetting::Custom { amount, gas_limit } => Fee::from_amount_and_gas(
-                        cosmrs::Coin {
-                            denom: amount.denom.parse().unwrap(),
-                            amount: amount.amount.to_string().parse().unwrap(),
-                        },
-                        *gas_limit,
-                    ),
-                };
+        unsafe { self.run_block(|| self.execute_tx(msgs.clone(), &[signer.into()], signer.into())) }
+    }
 
-                let tx = self.create_signed_tx(msgs.clone(), signer, fee)?;
+    fn execute_multiple_raw_multi_signer<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        signers: &[&SigningAccount],
+        fee_payer: &SigningAccount,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        let signers = signers.iter().map(|&s| s.into()).collect::<Vec<_>>();
+        unsafe { self.run_block(|| self.execute_tx(msgs.clone(), &signers, fee_payer.into())) }
+    }
 
-                let mut buf = Vec::new();
-                RequestDeliverTx::encode(&RequestDeliverTx { tx }, &mut buf)
-                    .map_err(EncodeError::ProtoEncodeError)?;
+    fn execute_multiple_raw_multisig<R>(
+        &self,
+        msgs: Vec<cosmrs::Any>,
+        multisig: &MultisigAccount,
+        signers: &[&SigningAccount],
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        let signer = TxSigner::Multisig(multisig, signers);
+        unsafe { self.run_block(|| self.execute_tx(msgs.clone(), &[signer], signer)) }
+    }
 
-                let base64_req = base64::encode(buf);
-                redefine_as_go_string!(base64_req);
+    fn execute_as<R>(&self, sender: &str, msgs: Vec<cosmrs::Any>) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        unsafe { self.execute_tx_body(ExecuteAs, sender, msgs) }
+    }
 
-                let res = Execute(self.id, base64_req);
+    fn execute_multiple_raw_as_contract<R>(
+        &self,
+        contract: &str,
+        msgs: Vec<cosmrs::Any>,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        unsafe { self.execute_tx_body(ExecuteAsContract, contract, msgs) }
+    }
+
+    fn wasm_sudo<R>(&self, contract: &str, msg: &[u8]) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        let msg = std::str::from_utf8(msg).map_err(DecodeError::Utf8Error)?;
+        redefine_as_go_string!(contract);
+        redefine_as_go_string!(msg);
+
+        unsafe {
+            self.run_block(|| {
+                let res = WasmSudo(self.id, contract, msg);
                 let res = RawResult::from_non_null_ptr(res).into_result()?;
 
                 ResponseDeliverTx::decode(res.as_slice())
@@ -375,6 +1133,10 @@ impl<'a> Runner<'a> for BaseApp {
         }
     }
 
+    fn stored_codes(&self) -> Option<&StoredCodes> {
+        Some(&self.stored_codes)
+    }
+
     fn query<Q, R>(&self, path: &str, q: &Q) -> RunnerResult<R>
     where
         Q: ::prost::Message,
@@ -1,7 +1,9 @@
+use cosmrs::proto::tendermint::abci::ResponseDeliverTx;
 use cosmrs::rpc::error::Error as TendermintRpcError;
 use cosmrs::tendermint::Error as TendermintError;
 use cosmrs::ErrorReport;
-use std::str::Utf8Error;
+use std::fmt::Display;
+use std::str::{FromStr, Utf8Error};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,6 +20,9 @@ pub enum RunnerError {
     #[error("execute error: {}", .msg)]
     ExecuteError { msg: String },
 
+    #[error("tx error: {}", .0.log)]
+    TxError(TxError),
+
     #[error("{0}")]
     GenericError(String),
 
@@ -29,6 +34,30 @@ pub enum RunnerError {
 
     #[error("{0}")]
     TendermintRpc(#[from] TendermintRpcError),
+
+    #[error("{0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl RunnerError {
+    /// `(codespace, code)` of a failed tx, to compare against [`crate::sdkerrors`] constants.
+    pub fn abci_code(&self) -> Option<(&str, u32)> {
+        match self {
+            RunnerError::TxError(err) => Some(err.code()),
+            _ => None,
+        }
+    }
+
+    /// Error returned by a contract, if this error is a failed contract execution or query.
+    pub fn contract_error(&self) -> Option<WasmContractError> {
+        match self {
+            RunnerError::TxError(err) => WasmContractError::parse(&err.log),
+            RunnerError::ExecuteError { msg } | RunnerError::QueryError { msg } => {
+                WasmContractError::parse(msg)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl PartialEq for RunnerError {
@@ -38,6 +67,7 @@ impl PartialEq for RunnerError {
             (RunnerError::DecodeError(a), RunnerError::DecodeError(b)) => a == b,
             (RunnerError::QueryError { msg: a }, RunnerError::QueryError { msg: b }) => a == b,
             (RunnerError::ExecuteError { msg: a }, RunnerError::ExecuteError { msg: b }) => a == b,
+            (RunnerError::TxError(a), RunnerError::TxError(b)) => a == b,
             (RunnerError::ErrorReport(a), RunnerError::ErrorReport(b)) => {
                 a.to_string() == b.to_string()
             }
@@ -45,11 +75,105 @@ impl PartialEq for RunnerError {
                 a.to_string() == b.to_string()
             }
             (RunnerError::TendermintRpc(a), RunnerError::TendermintRpc(b)) => a.0 == b.0,
+            (RunnerError::IoError(a), RunnerError::IoError(b)) => a.to_string() == b.to_string(),
             _ => false,
         }
     }
 }
 
+/// Failure of a tx, either when delivered or when simulated for fee estimation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxError {
+    pub codespace: String,
+    pub code: u32,
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+    pub log: String,
+    /// Index of the failing message, if the failure comes from one of them.
+    pub msg_index: Option<usize>,
+}
+
+impl TxError {
+    /// `(codespace, code)` of the error, to compare against [`crate::sdkerrors`] constants.
+    pub fn code(&self) -> (&str, u32) {
+        (&self.codespace, self.code)
+    }
+}
+
+impl From<ResponseDeliverTx> for TxError {
+    fn from(res: ResponseDeliverTx) -> Self {
+        TxError {
+            msg_index: parse_msg_index(&res.log),
+            codespace: res.codespace,
+            code: res.code,
+            gas_wanted: res.gas_wanted as u64,
+            gas_used: res.gas_used as u64,
+            log: res.log,
+        }
+    }
+}
+
+/// Message failures are logged as `failed to execute message; message index: {index}: {error}`.
+pub(crate) fn parse_msg_index(log: &str) -> Option<usize> {
+    let (_, rest) = log.split_once("message index: ")?;
+    let digits = rest
+        .split(|c: char| !c.is_ascii_digit())
+        .next()
+        .unwrap_or_default();
+    digits.parse().ok()
+}
+
+/// Error returned by a CosmWasm contract, extracted from an error such as
+/// `failed to execute message; message index: 0: Unauthorized: execute wasm contract failed`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmContractError {
+    /// The error message of the contract, e.g. `Unauthorized`.
+    pub msg: String,
+    /// Index of the failing message, `None` for queries.
+    pub msg_index: Option<usize>,
+}
+
+/// Errors wrapping contract errors in `x/wasm`.
+const WASM_CONTRACT_FAILURES: &[&str] = &[
+    "execute wasm contract failed",
+    "instantiate wasm contract failed",
+    "migrate wasm contract failed",
+    "query wasm contract failed",
+];
+
+impl WasmContractError {
+    /// Extract the contract error from an error log, `None` if the log is not a contract failure.
+    pub fn parse(log: &str) -> Option<Self> {
+        let msg = WASM_CONTRACT_FAILURES.iter().find_map(|failure| {
+            log.strip_suffix(failure)
+                .and_then(|rest| rest.strip_suffix(": "))
+        })?;
+        let msg_index = parse_msg_index(msg);
+        let msg = match msg.split_once("message index: ") {
+            Some((_, rest)) => {
+                let rest = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+                rest.strip_prefix(": ").unwrap_or(rest)
+            }
+            None => msg,
+        };
+
+        Some(WasmContractError {
+            msg: msg.to_string(),
+            msg_index,
+        })
+    }
+
+    /// Whether the contract failed with `err`, compared by their `Display` output.
+    pub fn matches<E: Display>(&self, err: &E) -> bool {
+        self.msg == err.to_string()
+    }
+
+    /// Parse the error message into a contract error type.
+    pub fn downcast<E: FromStr>(&self) -> Result<E, E::Err> {
+        self.msg.parse()
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum DecodeError {
     #[error("invalid utf8 bytes")]
@@ -93,6 +217,9 @@ pub enum EncodeError {
 
     #[error("unable to encode json")]
     JsonEncodeError(#[from] serde_json::Error),
+
+    #[error("unsupported message: {}", .msg)]
+    UnsupportedMessage { msg: String },
 }
 
 impl PartialEq for EncodeError {
@@ -102,6 +229,10 @@ impl PartialEq for EncodeError {
             (EncodeError::JsonEncodeError(a), EncodeError::JsonEncodeError(b)) => {
                 a.to_string() == b.to_string()
             }
+            (
+                EncodeError::UnsupportedMessage { msg: a },
+                EncodeError::UnsupportedMessage { msg: b },
+            ) => a == b,
             _ => false,
         }
     }
@@ -1,9 +1,10 @@
 use cosmwasm_std::CosmosMsg;
 use serde::de::DeserializeOwned;
 
-use crate::account::SigningAccount;
+use crate::account::{Account, MultisigAccount, SigningAccount};
+use crate::module::wasm::StoredCodes;
 use crate::runner::result::{RunnerExecuteResult, RunnerResult};
-use crate::utils::{bank_msg_to_any, wasm_msg_to_any};
+use crate::utils::cosmos_msg_to_any;
 use crate::RunnerError;
 
 pub mod app;
@@ -41,6 +42,36 @@ pub trait Runner<'a> {
     where
         R: ::prost::Message + Default;
 
+    /// Execute messages in a single tx signed by all of `signers`, which must be given in the
+    /// order the messages require their signatures. `fee_payer` pays the fee according to its
+    /// fee setting; it signs the tx as well and is added as the last signer if it is not one
+    /// of `signers` already.
+    fn execute_multiple_raw_multi_signer<R>(
+        &self,
+        _msgs: Vec<cosmrs::Any>,
+        _signers: &[&SigningAccount],
+        _fee_payer: &SigningAccount,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        Err(unsupported("execute_multiple_raw_multi_signer"))
+    }
+
+    /// Execute messages in a tx from `multisig`, which pays the fee according to its fee
+    /// setting. The tx is signed by `signers`, which must be members of the multisig.
+    fn execute_multiple_raw_multisig<R>(
+        &self,
+        _msgs: Vec<cosmrs::Any>,
+        _multisig: &MultisigAccount,
+        _signers: &[&SigningAccount],
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        Err(unsupported("execute_multiple_raw_multisig"))
+    }
+
     fn execute_cosmos_msgs<S>(
         &self,
         msgs: &[CosmosMsg],
@@ -51,22 +82,77 @@ pub trait Runner<'a> {
     {
         let msgs = msgs
             .iter()
-            .map(|msg| match msg {
-                CosmosMsg::Bank(msg) => bank_msg_to_any(msg, signer),
-                CosmosMsg::Stargate { type_url, value } => Ok(cosmrs::Any {
-                    type_url: type_url.clone(),
-                    value: value.0.clone(),
-                }),
-                CosmosMsg::Wasm(msg) => wasm_msg_to_any(msg, signer),
-                _ => todo!("unsupported cosmos msg variant"),
-            })
+            .map(|msg| cosmos_msg_to_any(msg, &signer.address()))
             .collect::<Result<Vec<_>, RunnerError>>()?;
 
         self.execute_multiple_raw(msgs, signer)
     }
 
+    /// Execute messages as `sender`, which can be any address: a module account, a contract
+    /// or an address from an exported state. Every message must have `sender` as its only
+    /// signer.
+    ///
+    /// No tx is built, so the whole ante handler is skipped, not only signature verification:
+    /// no fees are deducted, the sender's sequence is not checked nor incremented, the sender
+    /// account doesn't need to exist, and gas and tx size limits don't apply.
+    fn execute_as<R>(&self, _sender: &str, _msgs: Vec<cosmrs::Any>) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        Err(unsupported("execute_as"))
+    }
+
+    /// Execute messages with `contract` as their sender, the way the wasm module dispatches
+    /// the messages of a contract's `Response`. No tx is signed, so there are no fees
+    /// and no ante handler checks.
+    fn execute_multiple_raw_as_contract<R>(
+        &self,
+        _contract: &str,
+        _msgs: Vec<cosmrs::Any>,
+    ) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        Err(unsupported("execute_multiple_raw_as_contract"))
+    }
+
+    fn execute_cosmos_msgs_as_contract<S>(
+        &self,
+        contract: &str,
+        msgs: &[CosmosMsg],
+    ) -> RunnerExecuteResult<S>
+    where
+        S: ::prost::Message + Default,
+    {
+        let msgs = msgs
+            .iter()
+            .map(|msg| cosmos_msg_to_any(msg, contract))
+            .collect::<Result<Vec<_>, RunnerError>>()?;
+
+        self.execute_multiple_raw_as_contract(contract, msgs)
+    }
+
+    /// Call the `sudo` entry point of a contract with a JSON encoded message, the way other
+    /// modules of the chain do. The data set by the contract is the response's `data` field.
+    fn wasm_sudo<R>(&self, _contract: &str, _msg: &[u8]) -> RunnerExecuteResult<R>
+    where
+        R: ::prost::Message + Default,
+    {
+        Err(unsupported("wasm_sudo"))
+    }
+
+    /// Codes stored by `Wasm::store_code_from_file` in this runner's chain, `None` if the
+    /// runner does not keep track of them.
+    fn stored_codes(&self) -> Option<&StoredCodes> {
+        None
+    }
+
     fn query<Q, R>(&self, path: &str, query: &Q) -> RunnerResult<R>
     where
         Q: ::prost::Message,
         R: ::prost::Message + DeserializeOwned + Default;
 }
+
+fn unsupported(method: &str) -> RunnerError {
+    RunnerError::GenericError(format!("`{}` is not supported by this runner", method))
+}
@@ -1,5 +1,5 @@
-use crate::runner::error::{DecodeError, RunnerError};
-use cosmrs::proto::cosmos::base::abci::v1beta1::{GasInfo, TxMsgData};
+use crate::runner::error::{parse_msg_index, DecodeError, RunnerError, TxError};
+use cosmrs::proto::cosmos::base::abci::v1beta1::{GasInfo, MsgData, TxMsgData};
 use cosmrs::proto::tendermint::abci::ResponseDeliverTx;
 use cosmrs::rpc::endpoint::broadcast::tx_commit::Response as TxCommitResponse;
 use cosmwasm_std::{Attribute, Event};
@@ -17,10 +17,119 @@ where
 {
     pub data: R,
     pub raw_data: Vec<u8>,
+    /// Responses of all messages of the tx, in order. `data` is the decoded first one.
+    msg_responses: Vec<MsgData>,
     pub events: Vec<Event>,
     pub gas_info: GasInfo,
 }
 
+impl<R> ExecuteResponse<R>
+where
+    R: prost::Message + Default,
+{
+    /// Raw responses of all messages of the tx, in order.
+    pub fn msg_responses(&self) -> &[MsgData] {
+        &self.msg_responses
+    }
+
+    /// Decode the response of the message at `index`, e.g. a `MsgMintResponse`
+    /// for the second message of a `MsgCreateDenom` + `MsgMint` tx.
+    pub fn msg_response<T>(&self, index: usize) -> RunnerResult<T>
+    where
+        T: prost::Message + Default,
+    {
+        let msg_data = self.msg_responses.get(index).ok_or_else(|| {
+            RunnerError::GenericError(format!("no response for message index {}", index))
+        })?;
+
+        T::decode(msg_data.data.as_slice())
+            .map_err(DecodeError::ProtoDecodeError)
+            .map_err(RunnerError::DecodeError)
+    }
+
+    /// All events of type `ty`.
+    pub fn find_events(&self, ty: &str) -> Vec<&Event> {
+        self.events.iter().filter(|e| e.ty == ty).collect()
+    }
+
+    /// Value of the first `key` attribute among the events of type `ty`.
+    pub fn attribute(&self, ty: &str, key: &str) -> Option<&str> {
+        self.events
+            .iter()
+            .filter(|e| e.ty == ty)
+            .flat_map(|e| e.attributes.iter())
+            .find(|a| a.key == key)
+            .map(|a| a.value.as_str())
+    }
+
+    /// `wasm` and custom `wasm-*` events emitted by `contract`.
+    pub fn wasm_events_for(&self, contract: &str) -> Vec<&Event> {
+        self.events
+            .iter()
+            .filter(|e| e.ty == "wasm" || e.ty.starts_with("wasm-"))
+            .filter(|e| {
+                e.attributes
+                    .iter()
+                    .any(|a| a.key == "_contract_address" && a.value == contract)
+            })
+            .collect()
+    }
+
+    /// Whether an event of type `ty` has all of `attrs`.
+    pub fn has_event(&self, ty: &str, attrs: &[(&str, &str)]) -> bool {
+        self.events
+            .iter()
+            .filter(|e| e.ty == ty)
+            .any(|e| has_attributes(e, attrs))
+    }
+
+    /// Panics with the emitted events if no event of type `ty` has all of `attrs`.
+    #[track_caller]
+    pub fn assert_has_event(&self, ty: &str, attrs: &[(&str, &str)]) {
+        assert!(
+            self.has_event(ty, attrs),
+            "no `{}` event with attributes {:?}, emitted events: {:#?}",
+            ty,
+            attrs,
+            self.events
+        );
+    }
+
+    /// Events grouped by message, in the order of the messages of the tx.
+    ///
+    /// Each message's events start with the `message` event carrying its `action`,
+    /// events emitted before the first message (fees, signatures) are left out.
+    pub fn events_by_msg(&self) -> Vec<&[Event]> {
+        let starts = self
+            .events
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| {
+                e.ty == "message" && e.attributes.first().map(|a| a.key.as_str()) == Some("action")
+            })
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+
+        starts
+            .iter()
+            .enumerate()
+            .map(|(n, &start)| {
+                let end = starts.get(n + 1).copied().unwrap_or(self.events.len());
+                &self.events[start..end]
+            })
+            .collect()
+    }
+}
+
+fn has_attributes(event: &Event, attrs: &[(&str, &str)]) -> bool {
+    attrs.iter().all(|(key, value)| {
+        event
+            .attributes
+            .iter()
+            .any(|a| a.key == *key && a.value == *value)
+    })
+}
+
 impl<R> TryFrom<ResponseDeliverTx> for ExecuteResponse<R>
 where
     R: prost::Message + Default,
@@ -28,6 +137,10 @@ where
     type Error = RunnerError;
 
     fn try_from(res: ResponseDeliverTx) -> Result<Self, Self::Error> {
+        if res.code != 0 {
+            return Err(RunnerError::TxError(res.into()));
+        }
+
         let tx_msg_data =
             TxMsgData::decode(res.data.as_slice()).map_err(DecodeError::ProtoDecodeError)?;
 
@@ -61,6 +174,7 @@ where
         Ok(ExecuteResponse {
             data,
             raw_data: res.data,
+            msg_responses: tx_msg_data.data,
             events,
             gas_info: GasInfo {
                 gas_wanted: res.gas_wanted as u64,
@@ -78,6 +192,18 @@ where
 
     fn try_from(tx_commit_response: TxCommitResponse) -> Result<Self, Self::Error> {
         let res = tx_commit_response.deliver_tx;
+
+        if res.code.is_err() {
+            return Err(RunnerError::TxError(TxError {
+                codespace: res.codespace.to_string(),
+                code: res.code.value(),
+                gas_wanted: res.gas_wanted.value(),
+                gas_used: res.gas_used.value(),
+                log: res.log.to_string(),
+                msg_index: parse_msg_index(res.log.as_ref()),
+            }));
+        }
+
         let tx_msg_data = TxMsgData::decode(res.data.clone().unwrap().value().as_slice())
             .map_err(DecodeError::ProtoDecodeError)?;
 
@@ -113,6 +239,7 @@ where
         Ok(Self {
             data,
             raw_data: res.data.unwrap().value().clone(),
+            msg_responses: tx_msg_data.data,
             events,
             gas_info: GasInfo {
                 gas_wanted: res.gas_wanted.value(),
@@ -140,6 +267,7 @@ where
 ///   0 -> Ok
 ///   1 -> QueryError
 ///   2 -> ExecuteError
+///   3 -> TxError, with proto encoded `ResponseDeliverTx` as content
 ///
 /// The rest are undefined and remaining spaces are reserved for future use.
 #[derive(Debug)]
@@ -165,6 +293,12 @@ impl RawResult {
 
         if code == 0 {
             Some(Self(Ok(content.to_vec())))
+        } else if code == 3 {
+            let error = match ResponseDeliverTx::decode(content) {
+                Ok(res) => RunnerError::TxError(res.into()),
+                Err(e) => RunnerError::DecodeError(DecodeError::ProtoDecodeError(e)),
+            };
+            Some(Self(Err(error)))
         } else {
             let content_string = CString::new(content)
                 .unwrap()
@@ -0,0 +1,45 @@
+//! `(codespace, code)` of the cosmos-sdk root errors, as defined in `types/errors/errors.go`,
+//! to be compared with `RunnerError::abci_code` or `TxError::code`.
+
+pub const CODESPACE: &str = "sdk";
+
+pub const TX_DECODE: (&str, u32) = (CODESPACE, 2);
+pub const INVALID_SEQUENCE: (&str, u32) = (CODESPACE, 3);
+pub const UNAUTHORIZED: (&str, u32) = (CODESPACE, 4);
+pub const INSUFFICIENT_FUNDS: (&str, u32) = (CODESPACE, 5);
+pub const UNKNOWN_REQUEST: (&str, u32) = (CODESPACE, 6);
+pub const INVALID_ADDRESS: (&str, u32) = (CODESPACE, 7);
+pub const INVALID_PUB_KEY: (&str, u32) = (CODESPACE, 8);
+pub const UNKNOWN_ADDRESS: (&str, u32) = (CODESPACE, 9);
+pub const INVALID_COINS: (&str, u32) = (CODESPACE, 10);
+pub const OUT_OF_GAS: (&str, u32) = (CODESPACE, 11);
+pub const MEMO_TOO_LARGE: (&str, u32) = (CODESPACE, 12);
+pub const INSUFFICIENT_FEE: (&str, u32) = (CODESPACE, 13);
+pub const TOO_MANY_SIGNATURES: (&str, u32) = (CODESPACE, 14);
+pub const NO_SIGNATURES: (&str, u32) = (CODESPACE, 15);
+pub const JSON_MARSHAL: (&str, u32) = (CODESPACE, 16);
+pub const JSON_UNMARSHAL: (&str, u32) = (CODESPACE, 17);
+pub const INVALID_REQUEST: (&str, u32) = (CODESPACE, 18);
+pub const TX_IN_MEMPOOL_CACHE: (&str, u32) = (CODESPACE, 19);
+pub const MEMPOOL_IS_FULL: (&str, u32) = (CODESPACE, 20);
+pub const TX_TOO_LARGE: (&str, u32) = (CODESPACE, 21);
+pub const KEY_NOT_FOUND: (&str, u32) = (CODESPACE, 22);
+pub const WRONG_PASSWORD: (&str, u32) = (CODESPACE, 23);
+pub const INVALID_SIGNER: (&str, u32) = (CODESPACE, 24);
+pub const INVALID_GAS_ADJUSTMENT: (&str, u32) = (CODESPACE, 25);
+pub const INVALID_HEIGHT: (&str, u32) = (CODESPACE, 26);
+pub const INVALID_VERSION: (&str, u32) = (CODESPACE, 27);
+pub const INVALID_CHAIN_ID: (&str, u32) = (CODESPACE, 28);
+pub const INVALID_TYPE: (&str, u32) = (CODESPACE, 29);
+pub const TX_TIMEOUT_HEIGHT: (&str, u32) = (CODESPACE, 30);
+pub const UNKNOWN_EXTENSION_OPTIONS: (&str, u32) = (CODESPACE, 31);
+pub const WRONG_SEQUENCE: (&str, u32) = (CODESPACE, 32);
+pub const PACK_ANY: (&str, u32) = (CODESPACE, 33);
+pub const UNPACK_ANY: (&str, u32) = (CODESPACE, 34);
+pub const LOGIC: (&str, u32) = (CODESPACE, 35);
+pub const CONFLICT: (&str, u32) = (CODESPACE, 36);
+pub const NOT_SUPPORTED: (&str, u32) = (CODESPACE, 37);
+pub const NOT_FOUND: (&str, u32) = (CODESPACE, 38);
+pub const IO: (&str, u32) = (CODESPACE, 39);
+pub const APP_CONFIG: (&str, u32) = (CODESPACE, 40);
+pub const PANIC: (&str, u32) = (CODESPACE, 111222);
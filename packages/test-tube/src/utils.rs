@@ -1,11 +1,19 @@
 use cosmrs::proto::{
-    cosmos::bank::v1beta1::MsgSend,
+    cosmos::{
+        bank::v1beta1::MsgSend,
+        distribution::v1beta1::{MsgSetWithdrawAddress, MsgWithdrawDelegatorReward},
+        gov::v1beta1::{MsgVote, VoteOption as ProtoVoteOption},
+        staking::v1beta1::{MsgBeginRedelegate, MsgDelegate, MsgUndelegate},
+    },
     cosmwasm::wasm::v1::{
         MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgMigrateContract,
         MsgUpdateAdmin,
     },
+    ibc::{applications::transfer::v1::MsgTransfer, core::client::v1::Height},
+};
+use cosmwasm_std::{
+    BankMsg, Coin, CosmosMsg, DistributionMsg, GovMsg, IbcMsg, StakingMsg, VoteOption, WasmMsg,
 };
-use cosmwasm_std::{BankMsg, Coin, WasmMsg};
 use prost::Message;
 
 use crate::{Account, EncodeError, RunnerError, SigningAccount};
@@ -22,6 +30,17 @@ pub fn coins_to_proto(coins: &[Coin]) -> Vec<cosmrs::proto::cosmos::base::v1beta
         .collect()
 }
 
+fn coin_to_proto(coin: &Coin) -> cosmrs::proto::cosmos::base::v1beta1::Coin {
+    cosmrs::proto::cosmos::base::v1beta1::Coin {
+        denom: coin.denom.clone(),
+        amount: coin.amount.to_string(),
+    }
+}
+
+fn unsupported(msg: impl Into<String>) -> RunnerError {
+    EncodeError::UnsupportedMessage { msg: msg.into() }.into()
+}
+
 pub fn proto_coin_to_coin(proto_coin: &cosmrs::proto::cosmos::base::v1beta1::Coin) -> Coin {
     Coin {
         denom: proto_coin.denom.clone(),
@@ -45,23 +64,31 @@ pub fn msg_to_any<T: Message>(type_url: &str, msg: &T) -> Result<cosmrs::Any, Ru
 }
 
 pub fn bank_msg_to_any(msg: &BankMsg, signer: &SigningAccount) -> Result<cosmrs::Any, RunnerError> {
+    bank_msg_to_any_from(msg, &signer.address())
+}
+
+fn bank_msg_to_any_from(msg: &BankMsg, sender: &str) -> Result<cosmrs::Any, RunnerError> {
     match msg {
         BankMsg::Send { to_address, amount } => {
             let type_url = "/cosmos.bank.v1beta1.MsgSend";
             let msg = MsgSend {
-                from_address: signer.address(),
+                from_address: sender.to_string(),
                 to_address: to_address.to_string(),
                 amount: coins_to_proto(amount),
             };
             msg_to_any(type_url, &msg)
         }
-        _ => {
-            todo!() // TODO: Can't find BurnMsg...?
-        }
+        // cosmos-sdk v0.45 has no tx message for burning, only modules can burn
+        BankMsg::Burn { .. } => Err(unsupported("BankMsg::Burn")),
+        _ => Err(unsupported(format!("{:?}", msg))),
     }
 }
 
 pub fn wasm_msg_to_any(msg: &WasmMsg, signer: &SigningAccount) -> Result<cosmrs::Any, RunnerError> {
+    wasm_msg_to_any_from(msg, &signer.address())
+}
+
+fn wasm_msg_to_any_from(msg: &WasmMsg, sender: &str) -> Result<cosmrs::Any, RunnerError> {
     match msg {
         WasmMsg::Execute {
             contract_addr,
@@ -72,7 +99,7 @@ pub fn wasm_msg_to_any(msg: &WasmMsg, signer: &SigningAccount) -> Result<cosmrs:
             &MsgExecuteContract {
                 contract: contract_addr.clone(),
                 funds: coins_to_proto(funds),
-                sender: signer.address(),
+                sender: sender.to_string(),
                 msg: msg.to_vec(),
             },
         ),
@@ -85,7 +112,7 @@ pub fn wasm_msg_to_any(msg: &WasmMsg, signer: &SigningAccount) -> Result<cosmrs:
         } => msg_to_any(
             "/cosmwasm.wasm.v1.MsgInstantiateContract",
             &MsgInstantiateContract {
-                sender: signer.address(),
+                sender: sender.to_string(),
                 admin: admin.clone().unwrap_or_default(),
                 code_id: *code_id,
                 label: label.clone(),
@@ -101,7 +128,7 @@ pub fn wasm_msg_to_any(msg: &WasmMsg, signer: &SigningAccount) -> Result<cosmrs:
             "/cosmwasm.wasm.v1.MsgMigrateContract",
             &MsgMigrateContract {
                 contract: contract_addr.clone(),
-                sender: signer.address(),
+                sender: sender.to_string(),
                 code_id: *new_code_id,
                 msg: msg.to_vec(),
             },
@@ -113,7 +140,7 @@ pub fn wasm_msg_to_any(msg: &WasmMsg, signer: &SigningAccount) -> Result<cosmrs:
             "/cosmwasm.wasm.v1.MsgUpdateAdmin",
             &MsgUpdateAdmin {
                 contract: contract_addr.clone(),
-                sender: signer.address(),
+                sender: sender.to_string(),
                 new_admin: admin.clone(),
             },
         ),
@@ -121,11 +148,140 @@ pub fn wasm_msg_to_any(msg: &WasmMsg, signer: &SigningAccount) -> Result<cosmrs:
             "/cosmwasm.wasm.v1.MsgClearAdmin",
             &MsgClearAdmin {
                 contract: contract_addr.clone(),
-                sender: signer.address(),
+                sender: sender.to_string(),
+            },
+        ),
+        _ => Err(unsupported(format!("{:?}", msg))),
+    }
+}
+
+pub fn staking_msg_to_any(msg: &StakingMsg, sender: &str) -> Result<cosmrs::Any, RunnerError> {
+    match msg {
+        StakingMsg::Delegate { validator, amount } => msg_to_any(
+            "/cosmos.staking.v1beta1.MsgDelegate",
+            &MsgDelegate {
+                delegator_address: sender.to_string(),
+                validator_address: validator.clone(),
+                amount: Some(coin_to_proto(amount)),
+            },
+        ),
+        StakingMsg::Undelegate { validator, amount } => msg_to_any(
+            "/cosmos.staking.v1beta1.MsgUndelegate",
+            &MsgUndelegate {
+                delegator_address: sender.to_string(),
+                validator_address: validator.clone(),
+                amount: Some(coin_to_proto(amount)),
+            },
+        ),
+        StakingMsg::Redelegate {
+            src_validator,
+            dst_validator,
+            amount,
+        } => msg_to_any(
+            "/cosmos.staking.v1beta1.MsgBeginRedelegate",
+            &MsgBeginRedelegate {
+                delegator_address: sender.to_string(),
+                validator_src_address: src_validator.clone(),
+                validator_dst_address: dst_validator.clone(),
+                amount: Some(coin_to_proto(amount)),
+            },
+        ),
+        _ => Err(unsupported(format!("{:?}", msg))),
+    }
+}
+
+pub fn distribution_msg_to_any(
+    msg: &DistributionMsg,
+    sender: &str,
+) -> Result<cosmrs::Any, RunnerError> {
+    match msg {
+        DistributionMsg::SetWithdrawAddress { address } => msg_to_any(
+            "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress",
+            &MsgSetWithdrawAddress {
+                delegator_address: sender.to_string(),
+                withdraw_address: address.clone(),
+            },
+        ),
+        DistributionMsg::WithdrawDelegatorReward { validator } => msg_to_any(
+            "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+            &MsgWithdrawDelegatorReward {
+                delegator_address: sender.to_string(),
+                validator_address: validator.clone(),
+            },
+        ),
+        _ => Err(unsupported(format!("{:?}", msg))),
+    }
+}
+
+pub fn gov_msg_to_any(msg: &GovMsg, sender: &str) -> Result<cosmrs::Any, RunnerError> {
+    match msg {
+        GovMsg::Vote { proposal_id, vote } => {
+            let option = match vote {
+                VoteOption::Yes => ProtoVoteOption::Yes,
+                VoteOption::No => ProtoVoteOption::No,
+                VoteOption::Abstain => ProtoVoteOption::Abstain,
+                VoteOption::NoWithVeto => ProtoVoteOption::NoWithVeto,
+            };
+            msg_to_any(
+                "/cosmos.gov.v1beta1.MsgVote",
+                &MsgVote {
+                    proposal_id: *proposal_id,
+                    voter: sender.to_string(),
+                    option: option.into(),
+                },
+            )
+        }
+        // `VoteWeighted` with the `cosmwasm_1_2` feature
+        #[allow(unreachable_patterns)]
+        _ => Err(unsupported(format!("{:?}", msg))),
+    }
+}
+
+/// Only `IbcMsg::Transfer` has a tx message, packets and channels are handled by
+/// the IBC module on behalf of contracts.
+///
+/// The transfer is sent from the `transfer` port, the default port of ICS-20 transfers,
+/// since `IbcMsg::Transfer` has no source port. Channels bound to another port are not
+/// supported.
+pub fn ibc_msg_to_any(msg: &IbcMsg, sender: &str) -> Result<cosmrs::Any, RunnerError> {
+    match msg {
+        IbcMsg::Transfer {
+            channel_id,
+            to_address,
+            amount,
+            timeout,
+        } => msg_to_any(
+            "/ibc.applications.transfer.v1.MsgTransfer",
+            &MsgTransfer {
+                source_port: "transfer".to_string(),
+                source_channel: channel_id.clone(),
+                token: Some(coin_to_proto(amount)),
+                sender: sender.to_string(),
+                receiver: to_address.clone(),
+                timeout_height: timeout.block().map(|block| Height {
+                    revision_number: block.revision,
+                    revision_height: block.height,
+                }),
+                timeout_timestamp: timeout.timestamp().map(|t| t.nanos()).unwrap_or_default(),
             },
         ),
-        _ => Err(RunnerError::ExecuteError {
-            msg: "Unsupported WasmMsg".to_string(),
+        _ => Err(unsupported(format!("{:?}", msg))),
+    }
+}
+
+/// Convert a `CosmosMsg` into the tx message the chain executes for it on behalf of `sender`.
+pub fn cosmos_msg_to_any(msg: &CosmosMsg, sender: &str) -> Result<cosmrs::Any, RunnerError> {
+    match msg {
+        CosmosMsg::Bank(msg) => bank_msg_to_any_from(msg, sender),
+        CosmosMsg::Staking(msg) => staking_msg_to_any(msg, sender),
+        CosmosMsg::Distribution(msg) => distribution_msg_to_any(msg, sender),
+        CosmosMsg::Stargate { type_url, value } => Ok(cosmrs::Any {
+            type_url: type_url.clone(),
+            value: value.to_vec(),
         }),
+        CosmosMsg::Ibc(msg) => ibc_msg_to_any(msg, sender),
+        CosmosMsg::Wasm(msg) => wasm_msg_to_any_from(msg, sender),
+        CosmosMsg::Gov(msg) => gov_msg_to_any(msg, sender),
+        _ => Err(unsupported(format!("{:?}", msg))),
     }
 }